@@ -1,5 +1,10 @@
 use ggez::{event, ContextBuilder};
+use std::path::Path;
+mod camera;
 mod colors;
+mod edit;
+mod inspect;
+mod layers;
 mod render;
 fn main() {
     // GGEZ Context
@@ -8,7 +13,29 @@ fn main() {
         .expect("Could not create ggez context!");
 
     // Instantiate a renderer for GGEZ
-    let mut my_game = render::Render::new(&mut ctx);
+    // Either `--record <path>` to record a replay of the simulation, `--replay <path>` to play one back,
+    // `--map <path>` to simulate (and edit) a map file, or `--generate <seed>` to simulate a generated world
+    let args: Vec<String> = std::env::args().collect();
+    let my_game = match (args.get(1).map(String::as_str), args.get(2)) {
+        (Some("--record"), Some(path)) => render::Render::new_recording(&mut ctx, Path::new(path)),
+        (Some("--replay"), Some(path)) => render::Render::new_replay(&mut ctx, Path::new(path)),
+        (Some("--map"), Some(path)) => render::Render::new_from_map(&mut ctx, Path::new(path)),
+        (Some("--generate"), Some(seed)) => match seed.parse() {
+            Ok(seed) => Ok(render::Render::new_generated(&mut ctx, seed)),
+            Err(_) => {
+                println!("Invalid seed: {}", seed);
+                return;
+            }
+        },
+        _ => Ok(render::Render::new(&mut ctx)),
+    };
+    let mut my_game = match my_game {
+        Ok(my_game) => my_game,
+        Err(e) => {
+            println!("Could not open file: {}", e);
+            return;
+        }
+    };
 
     // Main Event loop
     match event::run(&mut ctx, &mut event_loop, &mut my_game) {
@@ -0,0 +1,114 @@
+use ggez::graphics::Rect;
+use ggez::nalgebra::Point2;
+use sim::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use sim::Coordinates;
+
+/// The smallest size a tile can be zoomed to, in pixels
+const MIN_TILE_SIZE: f32 = 1.0;
+/// The largest size a tile can be zoomed to, in pixels
+const MAX_TILE_SIZE: f32 = 128.0;
+/// The gap left around the world when fitting it to the window, in pixels
+const FIT_MARGIN: f32 = 8.0;
+
+/// The range of tiles that can be seen through the camera
+pub struct VisibleTiles {
+    min_x: u16,
+    min_y: u16,
+    /// Exclusive
+    max_x: u16,
+    /// Exclusive
+    max_y: u16,
+}
+
+impl VisibleTiles {
+    /// Returns true if the tile at the given position can be seen
+    pub fn contains(&self, coords: Coordinates) -> bool {
+        (self.min_x..self.max_x).contains(&coords.get_x_position_u16())
+            && (self.min_y..self.max_y).contains(&coords.get_y_position_u16())
+    }
+}
+
+/// Maps between world tiles and screen pixels, allowing the view to be zoomed and panned
+pub struct Camera {
+    /// The screen position of the top left corner of the world
+    offset: Point2<f32>,
+    /// The size of each tile on screen, in pixels
+    tile_size: f32,
+}
+
+impl Camera {
+    /// Creates a camera with the world anchored at the top left of the screen
+    pub fn new(tile_size: f32) -> Camera {
+        Camera {
+            offset: Point2::new(0.0, 0.0),
+            tile_size: tile_size.clamp(MIN_TILE_SIZE, MAX_TILE_SIZE),
+        }
+    }
+
+    /// The size of each tile on screen, in pixels
+    pub fn get_tile_size(&self) -> f32 {
+        self.tile_size
+    }
+
+    /// The screen position of the top left corner of the given tile
+    pub fn tile_to_screen(&self, coords: Coordinates) -> Point2<f32> {
+        Point2::new(
+            self.offset.x + coords.get_x_position_u16() as f32 * self.tile_size,
+            self.offset.y + coords.get_y_position_u16() as f32 * self.tile_size,
+        )
+    }
+
+    /// The tile under the given screen position, if it is inside the world
+    pub fn screen_to_tile(&self, x: f32, y: f32) -> Option<Coordinates> {
+        let tile_x = ((x - self.offset.x) / self.tile_size).floor();
+        let tile_y = ((y - self.offset.y) / self.tile_size).floor();
+        if tile_x < 0.0 || tile_y < 0.0 {
+            return None;
+        }
+        Coordinates::new(tile_x as u16, tile_y as u16)
+    }
+
+    /// Pans so the centre of the given tile is in the centre of the given screen area
+    pub fn centre_on(&mut self, coords: Coordinates, screen: Rect) {
+        let tile = self.tile_to_screen(coords);
+        self.offset.x += screen.x + screen.w / 2.0 - (tile.x + self.tile_size / 2.0);
+        self.offset.y += screen.y + screen.h / 2.0 - (tile.y + self.tile_size / 2.0);
+    }
+
+    /// Moves the view by the given amount of pixels
+    pub fn pan(&mut self, x_amount: f32, y_amount: f32) {
+        self.offset.x += x_amount;
+        self.offset.y += y_amount;
+    }
+
+    /// Scales the size of tiles by the given factor, keeping the given screen position over the same point in the world
+    pub fn zoom_at(&mut self, x: f32, y: f32, factor: f32) {
+        let tile_size = (self.tile_size * factor).clamp(MIN_TILE_SIZE, MAX_TILE_SIZE);
+        let scale = tile_size / self.tile_size;
+        self.offset.x = x - (x - self.offset.x) * scale;
+        self.offset.y = y - (y - self.offset.y) * scale;
+        self.tile_size = tile_size;
+    }
+
+    /// Zooms and pans so the whole world fits inside the given screen area, centred
+    pub fn fit(&mut self, screen: Rect) {
+        let tile_size = ((screen.w - 2.0 * FIT_MARGIN) / WORLD_WIDTH as f32)
+            .min((screen.h - 2.0 * FIT_MARGIN) / WORLD_HEIGHT as f32);
+        self.tile_size = tile_size.clamp(MIN_TILE_SIZE, MAX_TILE_SIZE);
+        self.offset.x = screen.x + (screen.w - WORLD_WIDTH as f32 * self.tile_size) / 2.0;
+        self.offset.y = screen.y + (screen.h - WORLD_HEIGHT as f32 * self.tile_size) / 2.0;
+    }
+
+    /// The tiles that are at least partly inside the given screen area
+    pub fn visible_tiles(&self, screen: Rect) -> VisibleTiles {
+        let to_tile = |pixels: f32, offset: f32, limit: u16| {
+            ((pixels - offset) / self.tile_size).clamp(0.0, limit as f32)
+        };
+        VisibleTiles {
+            min_x: to_tile(screen.x, self.offset.x, WORLD_WIDTH).floor() as u16,
+            min_y: to_tile(screen.y, self.offset.y, WORLD_HEIGHT).floor() as u16,
+            max_x: to_tile(screen.x + screen.w, self.offset.x, WORLD_WIDTH).ceil() as u16,
+            max_y: to_tile(screen.y + screen.h, self.offset.y, WORLD_HEIGHT).ceil() as u16,
+        }
+    }
+}
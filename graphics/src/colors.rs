@@ -1,27 +1,70 @@
+use ggez::graphics;
+use ggez::graphics::Color;
 use sim::ant::AntType;
+use sim::ant_settings::{DEFAULT_RESOURCE_SIZE, MAXIMUM_PHEROMONE_STRENGTH};
+use sim::palette;
+use sim::palette::Rgba;
 use sim::pheromone::{Pheromone, PheromoneType};
-use ggez::graphics::Color;
-pub use sim::ant_settings::{
-    MAXIMUM_PHEROMONE_STRENGTH
-};
+use sim::resource::{Resource, ResourceKind};
+
+/// Converts a colour from the shared palette into one ggez can draw
+pub fn to_color(rgba: Rgba) -> Color {
+    Color::from_rgba(rgba[0], rgba[1], rgba[2], rgba[3])
+}
 
 /// Returns the colour to render the given Ant Type as
 pub fn get_ant_color(ant: &AntType) -> Color {
-    match ant {
-        AntType::Scout => Color::from_rgb(0, 0, 255),
-        AntType::Worker => Color::from_rgb(50, 190, 190),
-    }
+    to_color(palette::get_ant_color(*ant))
 }
 
 /// Returns the color that the Pheromone should be rendered as
 ///
 /// The lightness depends on the strength of the Pheromone
 pub fn get_pheromone_color(pheromone: &Pheromone) -> Color {
-    let color = (200_f64 * ((pheromone.get_strength() as f64) / (MAXIMUM_PHEROMONE_STRENGTH as f64)))
-        as u8
-        + 55;
-    match pheromone.pheromone_type {
-        PheromoneType::Exploration => Color::from_rgb(color, 0, color),
-        PheromoneType::Resource => Color::from_rgb(color, color, color),
-    }
+    to_color(palette::get_pheromone_color(pheromone))
+}
+
+/// Returns the colour that the Resource should be rendered as
+///
+/// Each kind of resource has its own colour, and lighter shades are less depleted
+pub fn get_resource_color(resource: &Resource) -> Color {
+    to_color(palette::get_resource_color(resource))
+}
+
+/// Every colour in the legend, with what it represents
+pub fn legend() -> Vec<(&'static str, Color)> {
+    let pheromone = |pheromone_type| {
+        Pheromone::new(MAXIMUM_PHEROMONE_STRENGTH, 1, pheromone_type)
+            .map(|pheromone| get_pheromone_color(&pheromone))
+            .unwrap_or(graphics::WHITE)
+    };
+    vec![
+        (
+            "Exploration pheromone",
+            pheromone(PheromoneType::Exploration),
+        ),
+        ("Resource pheromone", pheromone(PheromoneType::Resource)),
+        ("Sugar (full)", get_resource_color(&Resource::default())),
+        (
+            "Sugar (nearly empty)",
+            get_resource_color(&Resource::new_with_capacity(
+                ResourceKind::Sugar,
+                1,
+                DEFAULT_RESOURCE_SIZE,
+            )),
+        ),
+        (
+            "Protein",
+            get_resource_color(&Resource::from_kind(ResourceKind::Protein)),
+        ),
+        (
+            "Water",
+            get_resource_color(&Resource::from_kind(ResourceKind::Water)),
+        ),
+        ("Colony", to_color(palette::COLONY_COLOR)),
+        ("Scout", get_ant_color(&AntType::Scout)),
+        ("Worker", get_ant_color(&AntType::Worker)),
+        ("Wall", to_color(palette::WALL_COLOR)),
+        ("Slow ground", to_color(palette::SLOW_COLOR)),
+    ]
 }
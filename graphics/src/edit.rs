@@ -0,0 +1,67 @@
+use sim::colony::Colony;
+use sim::resource::Resource;
+use sim::terrain::Terrain;
+use sim::world::World;
+use sim::Coordinates;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// What painting with the mouse changes, in edit mode
+#[derive(Copy, Clone, PartialEq)]
+pub enum Tool {
+    /// Places a resource with the chosen kind and amount, replacing any resource already there, unless the tile is a
+    /// wall
+    Food,
+    /// Places a wall, unless the tile has a colony or resource
+    Wall,
+    /// Places a colony, if there is not one on the tile already and the tile is not a wall
+    Colony,
+    /// Removes any resource, wall or colony
+    Erase,
+}
+
+impl Display for Tool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Tool::Food => write!(f, "Food"),
+            Tool::Wall => write!(f, "Wall"),
+            Tool::Colony => write!(f, "Colony"),
+            Tool::Erase => write!(f, "Erase"),
+        }
+    }
+}
+
+/// Applies the tool to the given tile of the world, placing the given food with the food tool
+///
+/// Returns the index the colony on the tile had, if it was erased
+pub fn paint(world: &mut World, tool: Tool, coords: Coordinates, food: Resource) -> Option<usize> {
+    let colony = world
+        .get_colonies()
+        .iter()
+        .position(|colony| colony.get_position() == coords);
+    let passable = world.get_terrain(coords).is_passable();
+    match tool {
+        Tool::Food => {
+            if passable {
+                world.set_resource(coords, food);
+            }
+        }
+        Tool::Wall => {
+            world.set_terrain(coords, Terrain::Wall);
+        }
+        Tool::Colony => {
+            if colony.is_none() && passable {
+                world.add_colony(Colony::new(coords));
+            }
+        }
+        Tool::Erase => {
+            world.remove_resource(coords);
+            world.set_terrain(coords, Terrain::Open);
+            if let Some(index) = colony {
+                world.remove_colony(index);
+                return Some(index);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,105 @@
+use sim::ant::Ant;
+use sim::ant_store::AntId;
+use sim::resource::RESOURCE_KINDS;
+use sim::snapshot::WorldSnapshot;
+use sim::world::World;
+use sim::Coordinates;
+use std::fmt::Write;
+
+/// What has been clicked on, to be shown in the side panel
+#[derive(Copy, Clone, PartialEq)]
+pub enum Selection {
+    /// Everything on a single tile
+    Tile(Coordinates),
+    /// A single ant, that the view follows across ticks
+    Ant { colony: usize, id: AntId },
+}
+
+/// Describes everything on the given tile of a live world, including the full state of every ant
+pub fn describe_tile(world: &World, coords: Coordinates) -> String {
+    let tile = world.get_tile(coords);
+    let mut text = format!("Tile {}\n", coords);
+    if let Some(colony) = tile.colony {
+        let _ = writeln!(text, "Colony {}", colony);
+        for kind in &RESOURCE_KINDS {
+            let _ = writeln!(
+                text,
+                "  {} stock: {}",
+                kind,
+                world.get_colonies()[colony].get_stock(*kind)
+            );
+        }
+    }
+    if let Some(resource) = tile.resource {
+        let _ = writeln!(
+            text,
+            "Resource: {}, {} of {} remaining",
+            resource.get_kind(),
+            resource.get_resources_remaining(),
+            resource.get_capacity()
+        );
+    }
+    for pheromone in tile.pheromones.values().flatten() {
+        let _ = writeln!(text, "Pheromone: {}", pheromone);
+    }
+    for info in &tile.ants {
+        text.push('\n');
+        text.push_str(&describe_ant(info.colony, info.id, &info.ant));
+    }
+    text
+}
+
+/// Describes the given ant, including its full internal state
+pub fn describe_ant(colony: usize, id: AntId, ant: &Ant) -> String {
+    format!(
+        "{} ant {} (colony {})\n  Position: {}\n  Steps on journey: {}\n  Returning to colony: {}\n  Found food: {}\n  Carrying: {}\n  Distance from colony: {:.2}\n",
+        ant.get_ant_type(),
+        id,
+        colony,
+        ant.get_position(),
+        ant.get_steps_on_current_journey(),
+        ant.is_returning_to_colony(),
+        ant.has_found_food(),
+        ant.get_carrying()
+            .map_or_else(|| String::from("Nothing"), |kind| kind.to_string()),
+        ant.get_distance_from_colony()
+    )
+}
+
+/// Describes everything on the given tile of a replay frame
+///
+/// Replays only record the type and position of ants, so their internal state can't be shown
+pub fn describe_snapshot_tile(snapshot: &WorldSnapshot, coords: Coordinates) -> String {
+    let mut text = format!("Tile {}\n", coords);
+    for (index, colony) in snapshot.get_colonies().iter().enumerate() {
+        if colony.position == coords {
+            let _ = writeln!(text, "Colony {}", index);
+        }
+    }
+    for (position, resource) in snapshot.iter_resources() {
+        if position == coords {
+            let _ = writeln!(
+                text,
+                "Resource: {}, {} of {} remaining",
+                resource.get_kind(),
+                resource.get_resources_remaining(),
+                resource.get_capacity()
+            );
+        }
+    }
+    for (position, pheromone) in snapshot.iter_pheromones() {
+        if position == coords {
+            let _ = writeln!(text, "Pheromone: {}", pheromone);
+        }
+    }
+    for (colony, ant) in snapshot.iter_ants() {
+        if ant.position == coords {
+            let _ = write!(
+                text,
+                "\n{} ant {} (colony {})\n  Position: {}\n",
+                ant.ant_type, ant.id, colony, ant.position
+            );
+        }
+    }
+    text
+}
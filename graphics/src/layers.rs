@@ -0,0 +1,108 @@
+use ggez::graphics::Color;
+use sim::ant::AntType;
+use sim::heatmap::HeatmapType;
+use sim::pheromone::PheromoneType;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// Everything that can be shown or hidden separately when drawing the world
+#[derive(Copy, Clone, PartialEq)]
+pub enum Layer {
+    Pheromones(PheromoneType),
+    Ants(AntType),
+    Colonies,
+    Resources,
+    Terrain,
+}
+
+impl Layer {
+    /// Every layer, in the order of the keys that toggle them (F1 to F7)
+    pub const ALL: [Layer; 7] = [
+        Layer::Pheromones(PheromoneType::Exploration),
+        Layer::Pheromones(PheromoneType::Resource),
+        Layer::Ants(AntType::Scout),
+        Layer::Ants(AntType::Worker),
+        Layer::Colonies,
+        Layer::Resources,
+        Layer::Terrain,
+    ];
+
+    /// The position of the layer in `Layer::ALL`
+    fn index(self) -> usize {
+        Layer::ALL.iter().position(|layer| *layer == self).unwrap()
+    }
+}
+
+impl Display for Layer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Layer::Pheromones(pheromone_type) => write!(f, "{} pheromones", pheromone_type),
+            Layer::Ants(ant_type) => write!(f, "{}s", ant_type),
+            Layer::Colonies => write!(f, "Colonies"),
+            Layer::Resources => write!(f, "Resources"),
+            Layer::Terrain => write!(f, "Terrain"),
+        }
+    }
+}
+
+/// Which layers are drawn, and which heatmap (if any) is drawn beneath them
+pub struct Layers {
+    shown: [bool; Layer::ALL.len()],
+    heatmap: Option<HeatmapType>,
+}
+
+impl Default for Layers {
+    fn default() -> Self {
+        Layers {
+            shown: [true; Layer::ALL.len()],
+            heatmap: None,
+        }
+    }
+}
+
+impl Layers {
+    /// Returns true if the given layer is drawn
+    pub fn is_shown(&self, layer: Layer) -> bool {
+        self.shown[layer.index()]
+    }
+
+    /// Shows the given layer if it is hidden, or hides it if it is shown
+    pub fn toggle(&mut self, layer: Layer) {
+        self.shown[layer.index()] = !self.shown[layer.index()];
+    }
+
+    /// Returns the heatmap drawn beneath the layers, if any
+    pub fn get_heatmap(&self) -> Option<HeatmapType> {
+        self.heatmap
+    }
+
+    /// Moves on to the next heatmap, going from no heatmap, to visits, to trails and back to none
+    pub fn next_heatmap(&mut self) {
+        self.heatmap = match self.heatmap {
+            None => Some(HeatmapType::Visits),
+            Some(HeatmapType::Visits) => Some(HeatmapType::Trails),
+            Some(HeatmapType::Trails) => None,
+        };
+    }
+
+    /// Describes every hidden layer and the heatmap, for the HUD
+    pub fn describe(&self) -> String {
+        let mut text = String::new();
+        if let Some(heatmap) = self.heatmap {
+            text.push_str(&format!("\nHeatmap: {}", heatmap));
+        }
+        for layer in Layer::ALL.iter().filter(|layer| !self.is_shown(**layer)) {
+            text.push_str(&format!("\nHidden: {}", layer));
+        }
+        text
+    }
+}
+
+/// Returns the colour to draw a tile of a heatmap as, from dark red for the lowest counts to yellow for the highest
+///
+/// The square root of the count is used, so tiles that are visited rarely can still be told apart from the
+/// busiest ones
+pub fn get_heat_color(count: u32, maximum: u32) -> Color {
+    let heat = (count as f32 / maximum.max(1) as f32).sqrt();
+    Color::new(0.3 + 0.7 * heat, heat, 0.0, 1.0)
+}
@@ -1,61 +1,710 @@
-pub use sim::ant_settings::{
-    DEBUG_MODE, DEFAULT_RESOURCE_SIZE, MAXIMUM_PHEROMONE_STRENGTH, WORLD_HEIGHT, WORLD_WIDTH,
+use crate::camera::{Camera, VisibleTiles};
+use crate::colors::{get_ant_color, get_pheromone_color, get_resource_color, legend, to_color};
+use crate::edit::{paint, Tool};
+use crate::inspect::{describe_ant, describe_snapshot_tile, describe_tile, Selection};
+use crate::layers::{get_heat_color, Layer, Layers};
+use ggez::event::{EventHandler, KeyCode, KeyMods, MouseButton};
+use ggez::graphics::spritebatch::SpriteBatch;
+use ggez::graphics::{
+    Color, DrawMode, DrawParam, Drawable, FilterMode, Image, Mesh, MeshBuilder, Rect, Scale, Text,
+    TextFragment,
 };
+use ggez::input::mouse;
+use ggez::nalgebra::{Point2, Vector2};
+use ggez::{event, graphics, timer, Context, GameResult};
+use sim::ant_settings::{DEFAULT_REPLAY_KEYFRAME_INTERVAL, DEFAULT_RESOURCE_SIZE};
+pub use sim::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use sim::ant_store::AntId;
+use sim::generation;
+use sim::generation::GeneratorSettings;
+use sim::heatmap::Heatmaps;
+use sim::map;
+use sim::palette::{get_terrain_color, BACKGROUND_COLOR, BORDER_COLOR, COLONY_COLOR};
+use sim::replay::{Recorder, Replay, ReplayPlayer};
+use sim::resource::{Resource, ResourceKind, RESOURCE_KINDS};
+use sim::snapshot::WorldSnapshot;
 use sim::world::World;
-use ggez::event::EventHandler;
-use ggez::graphics::spritebatch::SpriteBatch;
-use ggez::graphics::{Color, DrawParam, Drawable, Image};
-use ggez::nalgebra::Point2;
-use ggez::{graphics, Context, GameResult};
+use sim::Coordinates;
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
-use crate::colors::{get_ant_color,get_pheromone_color};
 
 /// This is the size of each individual tile in pixels
 const TILE_SIZE: u16 = 8;
+/// The time between ticks at each speed, from slowest to fastest
+///
+/// None runs as many ticks as fit in a single frame
+const TICK_INTERVALS: [Option<Duration>; 7] = [
+    Some(Duration::from_millis(1000)),
+    Some(Duration::from_millis(500)),
+    Some(Duration::from_millis(250)),
+    Some(Duration::from_millis(100)),
+    Some(Duration::from_millis(50)),
+    Some(Duration::from_millis(10)),
+    None,
+];
+/// The index of the speed the renderer starts at, a tick every quarter second
+const DEFAULT_SPEED: usize = 2;
+/// How much the view is zoomed by, for each step of the mouse wheel
+const ZOOM_FACTOR: f32 = 1.25;
+/// Where the map is saved to, if no map file was given
+const DEFAULT_MAP_PATH: &str = "map.txt";
+/// How long a resource takes to fade away after running out
+const FADE_DURATION: Duration = Duration::from_millis(1000);
+/// The smallest tile size, in pixels, that resource labels are drawn at
+const LABEL_TILE_SIZE: f32 = 16.0;
+/// The height of each line of the legend, in pixels
+const LEGEND_ROW_HEIGHT: f32 = 16.0;
+/// How far the mouse can move while held, for it to still count as a click rather than a drag, in pixels
+const CLICK_DISTANCE: f32 = 4.0;
+/// The longest time spent running ticks in a single frame, when running as fast as possible
+const FRAME_BUDGET: Duration = Duration::from_millis(16);
+
+/// Where the frames that are drawn come from
+enum Source {
+    /// A world that is simulated as it is drawn, and optionally recorded
+    Live {
+        world: Box<World>,
+        recorder: Option<Recorder<BufWriter<File>>>,
+    },
+    /// A recorded replay, that can be stepped, reversed and seeked
+    Replay {
+        player: ReplayPlayer,
+        reversed: bool,
+    },
+}
+
+/// Draws the world, and handles the keyboard controls
+///
+/// * Escape - Quit
+/// * Space - Pause or resume
+/// * Right/Period - Step one tick forwards, and pause
+/// * Plus/Minus - Speed up or slow down, up to as fast as possible
+/// * N - Reset with a new world, using a new seed, and finish any recording (only when simulating)
+/// * F - Fit the whole world to the window
+///
+/// The mouse wheel zooms around the cursor, and dragging with the left mouse button pans the view
+///
+/// Clicking a tile shows everything on it in a side panel, and clicking outside the world clears the selection
+/// * Tab - Select the next ant on the selected tile, and follow it across ticks
+///
+/// Each layer can be hidden, to see what is beneath it:
+/// * F1/F2 - Toggle exploration or resource pheromones
+/// * F3/F4 - Toggle scouts or workers
+/// * F5/F6/F7 - Toggle colonies, resources or terrain
+/// * L - Show or hide the amount left in each resource, when zoomed in far enough to read it
+/// * K - Show or hide the legend
+/// * H - Cycle between no heatmap, and heatmaps of the visits to each tile or the use of resource trails
+///
+/// When simulating, the map can be edited with the mouse, while paused or running:
+/// * E - Toggle edit mode, where clicking or dragging with the left mouse button paints with the current tool
+/// * 1/2/3/4 - Choose the food, wall, colony or erase tool (choosing food again changes the kind of food)
+/// * [/] - Decrease or increase the amount of food placed (by ten, while holding shift)
+/// * Ctrl+S - Save the map to the map file
+///
+/// The right mouse button always pans the view
+///
+/// When playing back a replay:
+/// * Left - Step one frame backwards, and pause
+/// * R - Reverse the direction of playback
+/// * Page Up/Page Down - Seek backwards or forwards by one keyframe interval
+/// * Home/End - Seek to the first or last frame
 pub struct Render {
-    world: World,
-    game_ticks: usize,
+    source: Source,
+    /// The frame currently being drawn
+    snapshot: WorldSnapshot,
+    paused: bool,
+    /// The index of the current speed, in TICK_INTERVALS
+    speed: usize,
     time_elapsed: Instant,
+    camera: Camera,
+    /// True while the left mouse button is held, and the view is being dragged
+    dragging: bool,
+    /// How far the mouse has moved since the left mouse button was pressed, in pixels
+    drag_distance: f32,
+    /// What is shown in the side panel
+    selection: Option<Selection>,
+    /// True while in edit mode
+    editing: bool,
+    /// The tool used to paint in edit mode
+    tool: Tool,
+    /// The amount of food placed by the food tool
+    food_amount: u32,
+    /// The kind of food placed by the food tool
+    food_kind: ResourceKind,
+    /// True while the left mouse button is held in edit mode
+    painting: bool,
+    /// Where the map is saved to
+    map_path: PathBuf,
+    /// Which layers and heatmap are drawn
+    layers: Layers,
+    /// Counts accumulated from every frame shown so far
+    heatmaps: Heatmaps,
+    /// True if the amount left in each resource is drawn on it
+    labels: bool,
+    /// True if the legend is drawn
+    legend: bool,
+    /// Resources that have run out, and when they did, while they fade away
+    fading: Vec<(Coordinates, Instant)>,
 }
 impl Render {
-    pub fn new(_ctx: &mut Context) -> Render {
-        let world = World::new();
-        Render {
-            world,
-            game_ticks: 0,
+    /// Creates a renderer that simulates a new world
+    pub fn new(ctx: &mut Context) -> Render {
+        Render::from_source(
+            ctx,
+            Source::Live {
+                world: Box::new(World::new()),
+                recorder: None,
+            },
+        )
+    }
+
+    /// Creates a renderer that simulates a new world, and records every tick to a replay file at the given path
+    ///
+    /// Resetting the world finishes the recording
+    pub fn new_recording(ctx: &mut Context, path: &Path) -> io::Result<Render> {
+        let mut render = Render::new(ctx);
+        if let Source::Live { world, recorder } = &mut render.source {
+            let writer = BufWriter::new(File::create(path)?);
+            *recorder = Some(Recorder::new(
+                world,
+                writer,
+                DEFAULT_REPLAY_KEYFRAME_INTERVAL,
+            )?);
+        }
+        Ok(render)
+    }
+
+    /// Creates a renderer that simulates the map file at the given path, or a new world if it does not exist yet
+    ///
+    /// Saving the map writes it back to the same path
+    pub fn new_from_map(ctx: &mut Context, path: &Path) -> io::Result<Render> {
+        let world = if path.exists() {
+            map::load(BufReader::new(File::open(path)?))?
+        } else {
+            World::new()
+        };
+        let mut render = Render::from_source(
+            ctx,
+            Source::Live {
+                world: Box::new(world),
+                recorder: None,
+            },
+        );
+        render.map_path = path.to_path_buf();
+        Ok(render)
+    }
+
+    /// Creates a renderer that simulates a world procedurally generated from the given seed
+    pub fn new_generated(ctx: &mut Context, seed: u64) -> Render {
+        Render::from_source(
+            ctx,
+            Source::Live {
+                world: Box::new(generation::generate(seed, &GeneratorSettings::default())),
+                recorder: None,
+            },
+        )
+    }
+
+    /// Creates a renderer that plays back the replay file at the given path
+    pub fn new_replay(ctx: &mut Context, path: &Path) -> io::Result<Render> {
+        let replay = Replay::read(BufReader::new(File::open(path)?))?;
+        let player = ReplayPlayer::new(replay)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "replay has no frames"))?;
+        Ok(Render::from_source(
+            ctx,
+            Source::Replay {
+                player,
+                reversed: false,
+            },
+        ))
+    }
+
+    fn from_source(ctx: &mut Context, source: Source) -> Render {
+        let mut render = Render {
+            source,
+            snapshot: WorldSnapshot::default(),
+            paused: false,
+            speed: DEFAULT_SPEED,
             time_elapsed: Instant::now(),
+            camera: Camera::new(TILE_SIZE as f32),
+            dragging: false,
+            drag_distance: 0.0,
+            selection: None,
+            editing: false,
+            tool: Tool::Food,
+            food_amount: DEFAULT_RESOURCE_SIZE,
+            food_kind: ResourceKind::Sugar,
+            painting: false,
+            map_path: PathBuf::from(DEFAULT_MAP_PATH),
+            layers: Layers::default(),
+            heatmaps: Heatmaps::new(),
+            labels: false,
+            legend: false,
+            fading: Vec::new(),
+        };
+        render.refresh_snapshot();
+        render.camera.fit(graphics::screen_coordinates(ctx));
+        render
+    }
+
+    /// Advances the source by one tick in the direction of playback, returning false if there was nothing to advance to
+    fn tick(&mut self) -> bool {
+        let reversed = matches!(self.source, Source::Replay { reversed: true, .. });
+        self.step(!reversed)
+    }
+
+    /// Moves the source one tick forwards or backwards, returning false if there was nothing to move to
+    ///
+    /// A simulated world can only move forwards
+    fn step(&mut self, forwards: bool) -> bool {
+        match &mut self.source {
+            Source::Live { .. } if !forwards => false,
+            Source::Live { world, recorder } => {
+                world.update();
+                self.heatmaps.record_world(world);
+                if let Some(writer) = recorder {
+                    if let Err(e) = writer.record(world) {
+                        println!("Stopped recording: {}", e);
+                        *recorder = None;
+                    }
+                }
+                true
+            }
+            Source::Replay { player, .. } => {
+                let stepped = if forwards {
+                    player.step_forward()
+                } else {
+                    player.step_backward()
+                };
+                self.heatmaps.record(player.get_snapshot());
+                stepped
+            }
+        }
+    }
+
+    /// Updates the frame being drawn, from the source
+    ///
+    /// Any resource that ran out as time moved forwards starts fading away
+    fn refresh_snapshot(&mut self) {
+        let previous = std::mem::replace(
+            &mut self.snapshot,
+            match &self.source {
+                Source::Live { world, .. } => WorldSnapshot::new(world),
+                Source::Replay { player, .. } => player.get_snapshot().clone(),
+            },
+        );
+        if self.snapshot.get_tick() > previous.get_tick() {
+            let now = Instant::now();
+            for (coords, _) in previous.iter_resources() {
+                if !self
+                    .snapshot
+                    .iter_resources()
+                    .any(|(position, _)| position == coords)
+                {
+                    self.fading.push((coords, now));
+                }
+            }
+        }
+        self.heatmaps.record(&self.snapshot);
+    }
+
+    /// Replaces a simulated world with a new one, using a new seed
+    ///
+    /// Any recording is finished first, as a replay only holds a single world
+    fn reset(&mut self) {
+        if let Source::Live { world, recorder } = &mut self.source {
+            if let Some(writer) = recorder.take() {
+                match writer.finish() {
+                    Ok(_) => println!("Finished recording, as the world was reset"),
+                    Err(e) => println!("Stopped recording: {}", e),
+                }
+            }
+            **world = World::new();
+            self.selection = None;
+        }
+    }
+
+    /// Adds a single coloured tile to the batch, if it can be seen through the camera
+    fn add_tile(
+        &self,
+        sprite: &mut SpriteBatch,
+        visible: &VisibleTiles,
+        coords: Coordinates,
+        color: Color,
+    ) {
+        if !visible.contains(coords) {
+            return;
+        }
+        let scale = self.camera.get_tile_size() / TILE_SIZE as f32;
+        sprite.add(DrawParam::src(
+            DrawParam::default()
+                .color(color)
+                .dest(self.camera.tile_to_screen(coords))
+                .scale(Vector2::new(scale, scale)),
+            graphics::Rect {
+                x: TILE_SIZE as f32,
+                y: TILE_SIZE as f32,
+                w: 1.0,
+                h: 1.0,
+            },
+        ));
+    }
+
+    /// Applies the current tool to the tile under the given screen position, if simulating
+    fn paint_at(&mut self, x: f32, y: f32) {
+        if let (Source::Live { world, .. }, Some(coords)) =
+            (&mut self.source, self.camera.screen_to_tile(x, y))
+        {
+            let food = Resource::new_of_kind(self.food_kind, self.food_amount);
+            if let Some(removed) = paint(world, self.tool, coords, food) {
+                // Removing a colony changes the index of every later colony
+                if let Some(Selection::Ant { colony, id }) = self.selection {
+                    self.selection = match colony.cmp(&removed) {
+                        Ordering::Less => self.selection,
+                        Ordering::Equal => None,
+                        Ordering::Greater => Some(Selection::Ant {
+                            colony: colony - 1,
+                            id,
+                        }),
+                    };
+                }
+            }
+            self.refresh_snapshot();
+        }
+    }
+
+    /// Writes the layout of a simulated world to the map file
+    fn save_map(&self) {
+        if let Source::Live { world, .. } = &self.source {
+            let result = File::create(&self.map_path)
+                .and_then(|file| map::save(world, BufWriter::new(file)));
+            match result {
+                Ok(()) => println!("Saved map to {}", self.map_path.display()),
+                Err(e) => println!("Could not save map: {}", e),
+            }
+        }
+    }
+
+    /// Handles the keys for editing the map, which are only used while simulating
+    fn edit_key(&mut self, keycode: KeyCode, keymods: KeyMods) {
+        match keycode {
+            KeyCode::E => self.editing = !self.editing,
+            // Choosing the food tool again moves on to the next kind of food
+            KeyCode::Key1 if self.editing && self.tool == Tool::Food => {
+                let index = RESOURCE_KINDS
+                    .iter()
+                    .position(|kind| *kind == self.food_kind)
+                    .unwrap_or(0);
+                self.food_kind = RESOURCE_KINDS[(index + 1) % RESOURCE_KINDS.len()];
+            }
+            KeyCode::Key1 | KeyCode::Key2 | KeyCode::Key3 | KeyCode::Key4 => {
+                self.editing = true;
+                self.tool = match keycode {
+                    KeyCode::Key1 => Tool::Food,
+                    KeyCode::Key2 => Tool::Wall,
+                    KeyCode::Key3 => Tool::Colony,
+                    _ => Tool::Erase,
+                };
+            }
+            KeyCode::LBracket | KeyCode::RBracket => {
+                let step = if keymods.contains(KeyMods::SHIFT) {
+                    10
+                } else {
+                    1
+                };
+                self.food_amount = if keycode == KeyCode::LBracket {
+                    self.food_amount.saturating_sub(step).max(1)
+                } else {
+                    self.food_amount.saturating_add(step)
+                };
+            }
+            KeyCode::S if keymods.contains(KeyMods::CTRL) => self.save_map(),
+            _ => {}
+        }
+    }
+
+    /// The position of the given ant in the current frame, if it still exists
+    fn find_ant(&self, colony: usize, id: AntId) -> Option<Coordinates> {
+        let ants = &self.snapshot.get_colonies().get(colony)?.ants;
+        let index = ants.binary_search_by_key(&id, |ant| ant.id).ok()?;
+        Some(ants[index].position)
+    }
+
+    /// The tile shown in the side panel
+    fn selected_tile(&self) -> Option<Coordinates> {
+        match self.selection? {
+            Selection::Tile(coords) => Some(coords),
+            Selection::Ant { colony, id } => self.find_ant(colony, id),
         }
     }
+
+    /// Selects the next ant on the selected tile, after the one already selected
+    fn select_next_ant(&mut self) {
+        let coords = match self.selected_tile() {
+            Some(coords) => coords,
+            None => return,
+        };
+        let ants: Vec<Selection> = self
+            .snapshot
+            .iter_ants()
+            .filter(|(_, ant)| ant.position == coords)
+            .map(|(colony, ant)| Selection::Ant { colony, id: ant.id })
+            .collect();
+        let next = match self
+            .selection
+            .and_then(|selection| ants.iter().position(|ant| *ant == selection))
+        {
+            Some(index) => (index + 1) % ants.len(),
+            None => 0,
+        };
+        if let Some(ant) = ants.get(next) {
+            self.selection = Some(*ant);
+        }
+    }
+
+    /// The text shown in the side panel, describing the selection
+    fn panel_text(&self) -> Option<String> {
+        let text = match (self.selection?, &self.source) {
+            (Selection::Tile(coords), Source::Live { world, .. }) => describe_tile(world, coords),
+            (Selection::Tile(coords), Source::Replay { .. }) => {
+                describe_snapshot_tile(&self.snapshot, coords)
+            }
+            (Selection::Ant { colony, id }, Source::Live { world, .. }) => {
+                let ant = world.get_colonies().get(colony)?.get_ants().get(id)?;
+                format!("Following\n{}", describe_ant(colony, id, &ant))
+            }
+            (Selection::Ant { colony, id }, Source::Replay { .. }) => format!(
+                "Following ant {} (colony {})\n{}",
+                id,
+                colony,
+                describe_snapshot_tile(&self.snapshot, self.find_ant(colony, id)?)
+            ),
+        };
+        Some(text)
+    }
+
+    /// The text shown in the corner of the window
+    fn hud_text(&self, ctx: &Context) -> String {
+        let source = match &self.source {
+            Source::Live { world, recorder } => format!(
+                "Seed: {}{}",
+                world.get_seed(),
+                if recorder.is_some() {
+                    " (recording)"
+                } else {
+                    ""
+                }
+            ),
+            Source::Replay { player, reversed } => format!(
+                "Frame: {}/{}{}",
+                player.get_index(),
+                player.get_replay().len() - 1,
+                if *reversed { " (reversed)" } else { "" }
+            ),
+        };
+        let edit = if self.editing {
+            match self.tool {
+                Tool::Food => format!(
+                    "\nEdit: {} ({}, {})",
+                    self.tool, self.food_kind, self.food_amount
+                ),
+                _ => format!("\nEdit: {}", self.tool),
+            }
+        } else {
+            String::new()
+        };
+        let speed = match TICK_INTERVALS[self.speed] {
+            Some(interval) => format!("{} ticks/s", 1000 / interval.as_millis()),
+            None => String::from("max"),
+        };
+        format!(
+            "Tick: {}\n{}\nSpeed: {}{}\nFPS: {:.0}{}{}",
+            self.snapshot.get_tick(),
+            source,
+            speed,
+            if self.paused { " (paused)" } else { "" },
+            timer::fps(ctx),
+            edit,
+            self.layers.describe()
+        )
+    }
 }
 impl EventHandler for Render {
     fn update(&mut self, _ctx: &mut Context) -> GameResult<()> {
-        // Does a game tick every quarter second
-        if Instant::now().duration_since(self.time_elapsed) > Duration::from_millis(250) {
-            println!("\n\n-----\nTick {}\n----", self.game_ticks);
-            if DEBUG_MODE {}
-            self.game_ticks += 1;
-            self.world.update();
-            if let Some(time) = self.time_elapsed.checked_add(Duration::from_millis(250)) {
-                self.time_elapsed = time;
+        if self.paused {
+            self.time_elapsed = Instant::now();
+            return Ok(());
+        }
+        match TICK_INTERVALS[self.speed] {
+            Some(interval) => {
+                if Instant::now().duration_since(self.time_elapsed) > interval {
+                    if self.tick() {
+                        self.refresh_snapshot();
+                    }
+                    if let Some(time) = self.time_elapsed.checked_add(interval) {
+                        self.time_elapsed = time;
+                    }
+                }
+            }
+            None => {
+                let start = Instant::now();
+                // Every tick still adds to the heatmaps, but the frame is only snapshotted once it is drawn
+                let mut ticked = false;
+                while start.elapsed() < FRAME_BUDGET && self.tick() {
+                    ticked = true;
+                }
+                if ticked {
+                    self.refresh_snapshot();
+                }
             }
         }
         Ok(())
     }
 
+    fn key_down_event(
+        &mut self,
+        ctx: &mut Context,
+        keycode: KeyCode,
+        keymods: KeyMods,
+        _repeat: bool,
+    ) {
+        match keycode {
+            KeyCode::Escape => event::quit(ctx),
+            KeyCode::Space => self.paused = !self.paused,
+            KeyCode::Right | KeyCode::Period => {
+                self.paused = true;
+                self.step(true);
+            }
+            KeyCode::Equals | KeyCode::Add => {
+                self.speed = (self.speed + 1).min(TICK_INTERVALS.len() - 1);
+                self.time_elapsed = Instant::now();
+            }
+            KeyCode::Minus | KeyCode::Subtract => {
+                self.speed = self.speed.saturating_sub(1);
+                self.time_elapsed = Instant::now();
+            }
+            KeyCode::N => self.reset(),
+            KeyCode::Tab => self.select_next_ant(),
+            KeyCode::F => self.camera.fit(graphics::screen_coordinates(ctx)),
+            KeyCode::F1 => self.layers.toggle(Layer::ALL[0]),
+            KeyCode::F2 => self.layers.toggle(Layer::ALL[1]),
+            KeyCode::F3 => self.layers.toggle(Layer::ALL[2]),
+            KeyCode::F4 => self.layers.toggle(Layer::ALL[3]),
+            KeyCode::F5 => self.layers.toggle(Layer::ALL[4]),
+            KeyCode::F6 => self.layers.toggle(Layer::ALL[5]),
+            KeyCode::F7 => self.layers.toggle(Layer::ALL[6]),
+            KeyCode::H => self.layers.next_heatmap(),
+            KeyCode::L => self.labels = !self.labels,
+            KeyCode::K => self.legend = !self.legend,
+            _ => match &mut self.source {
+                Source::Live { .. } => self.edit_key(keycode, keymods),
+                Source::Replay { player, reversed } => {
+                    let interval = player.get_replay().get_keyframe_interval() as usize;
+                    let last = player.get_replay().len() - 1;
+                    match keycode {
+                        KeyCode::Left => {
+                            self.paused = true;
+                            player.step_backward();
+                        }
+                        KeyCode::R => *reversed = !*reversed,
+                        KeyCode::PageUp => {
+                            player.seek(player.get_index().saturating_sub(interval));
+                        }
+                        KeyCode::PageDown => {
+                            player.seek((player.get_index() + interval).min(last));
+                        }
+                        KeyCode::Home => {
+                            player.seek(0);
+                        }
+                        KeyCode::End => {
+                            player.seek(last);
+                        }
+                        _ => {}
+                    }
+                }
+            },
+        }
+        self.refresh_snapshot();
+    }
+
+    fn mouse_button_down_event(&mut self, _ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
+        match button {
+            MouseButton::Left if self.editing => {
+                self.painting = true;
+                self.paint_at(x, y);
+            }
+            MouseButton::Left | MouseButton::Right => {
+                self.dragging = true;
+                self.drag_distance = 0.0;
+            }
+            _ => {}
+        }
+    }
+
+    fn mouse_button_up_event(&mut self, _ctx: &mut Context, button: MouseButton, x: f32, y: f32) {
+        match button {
+            MouseButton::Left if self.painting => self.painting = false,
+            MouseButton::Left => {
+                self.dragging = false;
+                if self.drag_distance < CLICK_DISTANCE {
+                    self.selection = self.camera.screen_to_tile(x, y).map(Selection::Tile);
+                }
+            }
+            MouseButton::Right => self.dragging = false,
+            _ => {}
+        }
+    }
+
+    fn mouse_motion_event(&mut self, _ctx: &mut Context, x: f32, y: f32, dx: f32, dy: f32) {
+        if self.painting && self.tool != Tool::Colony {
+            self.paint_at(x, y);
+        } else if self.dragging {
+            self.camera.pan(dx, dy);
+            self.drag_distance += dx.abs() + dy.abs();
+        }
+    }
+
+    fn mouse_wheel_event(&mut self, ctx: &mut Context, _x: f32, y: f32) {
+        let position = mouse::position(ctx);
+        self.camera
+            .zoom_at(position.x, position.y, ZOOM_FACTOR.powf(y));
+    }
+
+    fn resize_event(&mut self, ctx: &mut Context, width: f32, height: f32) {
+        // Keeps one screen unit to one pixel, rather than stretching the view
+        if let Err(e) = graphics::set_screen_coordinates(ctx, Rect::new(0.0, 0.0, width, height)) {
+            println!("Could not resize the view: {}", e);
+        }
+    }
+
     fn draw(&mut self, ctx: &mut Context) -> GameResult<()> {
-        graphics::clear(ctx, graphics::BLACK);
+        graphics::clear(ctx, to_color(BACKGROUND_COLOR));
         let img: Image = Image::from_rgba8(
             ctx,
-            (TILE_SIZE) as u16,
-            (TILE_SIZE) as u16,
+            TILE_SIZE,
+            TILE_SIZE,
             &vec![255; 4 * TILE_SIZE as usize * TILE_SIZE as usize],
         )?;
         let mut sprite = SpriteBatch::new(img);
+        let screen = graphics::screen_coordinates(ctx);
+        if let Some(Selection::Ant { colony, id }) = self.selection {
+            match self.find_ant(colony, id) {
+                Some(coords) => self.camera.centre_on(coords, screen),
+                None => self.selection = None,
+            }
+        }
+        let visible = self.camera.visible_tiles(screen);
+        let scale = self.camera.get_tile_size() / TILE_SIZE as f32;
         // Draw Borders
         sprite.add(DrawParam::src(
             DrawParam::default()
-                .color(Color::from_rgb(128, 128, 128))
-                .dest(Point2::new(0_f32, 0_f32)),
+                .color(to_color(BORDER_COLOR))
+                .dest(self.camera.tile_to_screen(Coordinates::default()))
+                .scale(Vector2::new(scale, scale)),
             graphics::Rect {
                 x: 0_f32,
                 y: 0_f32,
@@ -63,97 +712,162 @@ impl EventHandler for Render {
                 h: WORLD_HEIGHT as f32,
             },
         ));
+        // Draw Heatmap
+        if let Some(heatmap_type) = self.layers.get_heatmap() {
+            let maximum = self.heatmaps.get_maximum(heatmap_type);
+            for (coords, count) in self.heatmaps.iter(heatmap_type) {
+                self.add_tile(
+                    &mut sprite,
+                    &visible,
+                    coords,
+                    get_heat_color(count, maximum),
+                );
+            }
+        }
+        // Draw Walls and slow ground
+        if self.layers.is_shown(Layer::Terrain) {
+            for (coords, terrain) in self.snapshot.iter_terrain() {
+                if let Some(color) = get_terrain_color(terrain) {
+                    self.add_tile(&mut sprite, &visible, coords, to_color(color));
+                }
+            }
+        }
         // Draw Pheromones
-        for (coords, pheromone_type) in &self.world.pheromone_lookup {
-            if let Some(pheromone) = &self.world.pheromones[coords.get_x_position_usize()]
-                [coords.get_y_position_usize()][*pheromone_type]
+        for (coords, pheromone) in self.snapshot.iter_pheromones() {
+            if !self
+                .layers
+                .is_shown(Layer::Pheromones(pheromone.pheromone_type))
             {
-                sprite.add(DrawParam::src(
-                    DrawParam::default()
-                        .color(get_pheromone_color(pheromone))
-                        .dest(Point2::new(
-                            TILE_SIZE as f32 * (coords.get_x_position_u16()) as f32,
-                            TILE_SIZE as f32 * (coords.get_y_position_u16()) as f32,
-                        )),
-                    graphics::Rect {
-                        x: TILE_SIZE as f32,
-                        y: TILE_SIZE as f32,
-                        w: 1.0,
-                        h: 1.0,
-                    },
-                ));
+                continue;
             }
+            self.add_tile(
+                &mut sprite,
+                &visible,
+                coords,
+                get_pheromone_color(pheromone),
+            );
         }
         // Draw Ants
-        for colony in &self.world.colonies {
-            for (ant_type, ants) in colony.iter_ants() {
-                let colour = get_ant_color(ant_type);
-                for ant in ants {
-                    sprite.add(DrawParam::src(
-                        DrawParam::default().color(colour).dest(Point2::new(
-                            TILE_SIZE as f32 * (ant.position.get_x_position_u16()) as f32,
-                            TILE_SIZE as f32 * (ant.position.get_y_position_u16()) as f32,
-                        )),
-                        graphics::Rect {
-                            x: TILE_SIZE as f32,
-                            y: TILE_SIZE as f32,
-                            w: 1.0,
-                            h: 1.0,
-                        },
-                    ));
-                }
+        for (_colony, ant) in self.snapshot.iter_ants() {
+            if !self.layers.is_shown(Layer::Ants(ant.ant_type)) {
+                continue;
+            }
+            self.add_tile(
+                &mut sprite,
+                &visible,
+                ant.position,
+                get_ant_color(&ant.ant_type),
+            );
+        }
+        // Draw Colonies
+        if self.layers.is_shown(Layer::Colonies) {
+            for colony in self.snapshot.get_colonies() {
+                self.add_tile(
+                    &mut sprite,
+                    &visible,
+                    colony.position,
+                    to_color(COLONY_COLOR),
+                );
             }
-            sprite.add(DrawParam::src(
-                DrawParam::default()
-                    .color(Color::from_rgb(255, 0, 0))
-                    .dest(Point2::new(
-                        TILE_SIZE as f32 * (colony.get_position().get_y_position_u16()) as f32,
-                        TILE_SIZE as f32 * (colony.get_position().get_y_position_u16()) as f32,
-                    )),
-                graphics::Rect {
-                    x: TILE_SIZE as f32,
-                    y: TILE_SIZE as f32,
-                    w: 1.0,
-                    h: 1.0,
-                },
-            ));
         }
         // Draw Resources
-        for coords in &self.world.resource_lookup {
-            if let Some(_resource) =
-                &self.world.resources[coords.get_x_position_usize()][coords.get_y_position_usize()]
-            {
-                sprite.add(DrawParam::src(
-                    DrawParam::default()
-                        .color(Color::from_rgb(
-                            220, 220,
-                            40, /*                            0,
-                               (200_f64
-                                   * ((resource.get_percentage_remaining())
-                                       / (DEFAULT_RESOURCE_SIZE as f64)))
-                                   as u8
-                                   + 55,
-                               (200_f64
-                                   * ((resource.get_percentage_remaining())
-                                       / (DEFAULT_RESOURCE_SIZE as f64)))
-                                   as u8
-                                   + 55,*/
-                        ))
-                        .dest(Point2::new(
-                            TILE_SIZE as f32 * (coords.get_x_position_u16()) as f32,
-                            TILE_SIZE as f32 * (coords.get_y_position_u16()) as f32,
-                        )),
-                    graphics::Rect {
-                        x: TILE_SIZE as f32,
-                        y: TILE_SIZE as f32,
-                        w: 1.0,
-                        h: 1.0,
-                    },
-                ));
+        if self.layers.is_shown(Layer::Resources) {
+            for (coords, resource) in self.snapshot.iter_resources() {
+                self.add_tile(&mut sprite, &visible, coords, get_resource_color(resource));
+            }
+            // Resources that just ran out fade away, rather than vanishing
+            let empty = get_resource_color(&Resource::new(0));
+            for (coords, depleted_at) in &self.fading {
+                let fade = 1.0 - depleted_at.elapsed().as_secs_f32() / FADE_DURATION.as_secs_f32();
+                if fade > 0.0 {
+                    let color = Color::new(empty.r, empty.g, empty.b, fade);
+                    self.add_tile(&mut sprite, &visible, *coords, color);
+                }
             }
         }
+        self.fading
+            .retain(|(_, depleted_at)| depleted_at.elapsed() < FADE_DURATION);
 
         sprite.draw(ctx, graphics::DrawParam::default())?;
+
+        // Draw Resource Labels
+        let tile_size = self.camera.get_tile_size();
+        if self.labels && self.layers.is_shown(Layer::Resources) && tile_size >= LABEL_TILE_SIZE {
+            for (coords, resource) in self.snapshot.iter_resources() {
+                if !visible.contains(coords) {
+                    continue;
+                }
+                let label = Text::new(
+                    TextFragment::new(resource.get_resources_remaining().to_string())
+                        .scale(Scale::uniform(tile_size * 0.5)),
+                );
+                let corner = self.camera.tile_to_screen(coords);
+                graphics::queue_text(
+                    ctx,
+                    &label,
+                    Point2::new(corner.x + 1.0, corner.y + tile_size * 0.25),
+                    Some(graphics::BLACK),
+                );
+            }
+            graphics::draw_queued_text(ctx, DrawParam::default(), None, FilterMode::Linear)?;
+        }
+
+        // Draw Selection
+        if let Some(coords) = self.selected_tile() {
+            let corner = self.camera.tile_to_screen(coords);
+            let size = self.camera.get_tile_size();
+            let outline = Mesh::new_rectangle(
+                ctx,
+                DrawMode::stroke(2.0),
+                Rect::new(corner.x, corner.y, size, size),
+                Color::from_rgb(0, 255, 255),
+            )?;
+            graphics::draw(ctx, &outline, DrawParam::default())?;
+        }
+        if let Some(text) = self.panel_text() {
+            graphics::draw(
+                ctx,
+                &Text::new(text),
+                DrawParam::default().dest(Point2::new(screen.x + 8.0, screen.y + 8.0)),
+            )?;
+        }
+
+        // Draw Legend
+        if self.legend {
+            let entries = legend();
+            let bottom = screen.y + screen.h - 8.0;
+            let top = bottom - entries.len() as f32 * LEGEND_ROW_HEIGHT;
+            let mut swatches = MeshBuilder::new();
+            for (row, (name, color)) in entries.iter().enumerate() {
+                let y = top + row as f32 * LEGEND_ROW_HEIGHT;
+                swatches.rectangle(
+                    DrawMode::fill(),
+                    Rect::new(screen.x + 8.0, y + 2.0, 12.0, 12.0),
+                    *color,
+                );
+                graphics::queue_text(
+                    ctx,
+                    &Text::new(*name),
+                    Point2::new(screen.x + 26.0, y),
+                    None,
+                );
+            }
+            let swatches = swatches.build(ctx)?;
+            graphics::draw(ctx, &swatches, DrawParam::default())?;
+            graphics::draw_queued_text(ctx, DrawParam::default(), None, FilterMode::Linear)?;
+        }
+
+        // Draw HUD
+        let hud = Text::new(self.hud_text(ctx));
+        let hud_width = hud.width(ctx) as f32;
+        graphics::draw(
+            ctx,
+            &hud,
+            DrawParam::default().dest(Point2::new(
+                screen.x + screen.w - hud_width - 8.0,
+                screen.y + 8.0,
+            )),
+        )?;
         graphics::present(ctx)?;
         Ok(())
     }
@@ -0,0 +1,102 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::SeedableRng;
+use rand_pcg::Pcg64Mcg;
+use sim::ant::{Ant, AntType, ANT_TYPES};
+use sim::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use sim::ant_store::AntStore;
+use sim::colony::Colony;
+use sim::world::World;
+use sim::Coordinates;
+use std::collections::HashMap;
+
+/// The population sizes to compare the layouts at
+const POPULATIONS: [usize; 3] = [1_000, 10_000, 100_000];
+/// The seed of the world used to measure whole updates
+const SEED: u64 = 0x5EED;
+/// The number of time steps to run before measuring, so every ant has spawned and left the colony
+const WARM_UP_TICKS: usize = 20;
+
+type TileCounts = [[u32; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
+
+/// Builds the same population in the previous layout (a vector of ants per type) and in an [`AntStore`]
+fn build_populations(size: usize) -> (HashMap<AntType, Vec<Ant>>, AntStore) {
+    let mut rng = Pcg64Mcg::seed_from_u64(size as u64);
+    let colony_position = Coordinates::new(WORLD_WIDTH / 2, WORLD_HEIGHT / 2).unwrap();
+    let mut by_type: HashMap<AntType, Vec<Ant>> = HashMap::new();
+    let mut store = AntStore::new(colony_position);
+    for index in 0..size {
+        let ant_type = if index % 3 == 0 {
+            AntType::Worker
+        } else {
+            AntType::Scout
+        };
+        let position = Coordinates::new_random_from(&mut rng);
+        by_type
+            .entry(ant_type)
+            .or_default()
+            .push(Ant::new(ant_type, position, colony_position));
+        store.push(ant_type, position);
+    }
+    (by_type, store)
+}
+
+/// Counts the ants on every tile, as done at the start of every time step
+fn bench_count_ants(c: &mut Criterion) {
+    let mut group = c.benchmark_group("count_ants_per_tile");
+    for size in &POPULATIONS {
+        let (by_type, store) = build_populations(*size);
+        group.bench_with_input(
+            BenchmarkId::new("vec_per_type", size),
+            &by_type,
+            |b, ants| {
+                b.iter(|| {
+                    let mut counts: TileCounts = [[0; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
+                    for ant in ants.values().flatten() {
+                        let position = ant.get_position();
+                        counts[position.get_x_position_usize()][position.get_y_position_usize()] +=
+                            1;
+                    }
+                    black_box(counts)
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("ant_store", size), &store, |b, store| {
+            b.iter(|| {
+                let mut counts: TileCounts = [[0; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
+                for position in store.positions() {
+                    counts[position.get_x_position_usize()][position.get_y_position_usize()] += 1;
+                }
+                black_box(counts)
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Runs a full time step with the whole population stored in the colony's [`AntStore`]
+///
+/// Deciding each move still copies the ant out of the columns and back, so this measures how the update scales with
+/// the population, rather than a gain over the previous layout
+fn bench_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("world_update_population");
+    group.sample_size(10);
+    for size in &POPULATIONS {
+        let mut colony = Colony::default();
+        colony.set_spawn_rate(u16::MAX);
+        for ant_type in &ANT_TYPES {
+            colony.set_target_population(*ant_type, (*size / ANT_TYPES.len()) as u16);
+        }
+        let mut world = World::new_with_data(Vec::new(), vec![colony]);
+        world.set_seed(SEED);
+        for _ in 0..WARM_UP_TICKS {
+            world.update();
+        }
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| world.update())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_count_ants, bench_update);
+criterion_main!(benches);
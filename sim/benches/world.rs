@@ -0,0 +1,127 @@
+//! Benchmarks for the hot paths of the simulation
+//!
+//! Every workload is built from a fixed seed, so results are comparable between runs. The world dimensions are
+//! compile time constants (see [`ant_settings`](sim::ant_settings)), so world "size" is varied by how much of the
+//! world is populated with resources
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rand::SeedableRng;
+use rand_pcg::Pcg64Mcg;
+use sim::ant::ANT_TYPES;
+use sim::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use sim::colony::Colony;
+use sim::pheromone::Pheromone;
+use sim::resource::Resource;
+use sim::world::World;
+use sim::Coordinates;
+
+/// The seed every workload is generated from
+const SEED: u64 = 0x5EED;
+/// The number of time steps to run before measuring, so ants have spawned and laid pheromones
+const WARM_UP_TICKS: usize = 50;
+/// The number of ants of each type, per colony
+const ANT_COUNTS: [u16; 3] = [10, 100, 1_000];
+/// The fraction of tiles that contain a resource
+const RESOURCE_DENSITIES: [f64; 3] = [0.02, 0.1, 0.5];
+/// The fraction of tiles that contain a pheromone of each type
+const PHEROMONE_DENSITIES: [f64; 3] = [0.1, 0.5, 1.0];
+
+/// Places the given number of resources on distinct tiles, chosen with a seeded random number generator
+fn seeded_resources(count: usize) -> Vec<(Coordinates, Resource)> {
+    let mut rng = Pcg64Mcg::seed_from_u64(SEED);
+    let mut resources: Vec<(Coordinates, Resource)> = Vec::with_capacity(count);
+    while resources.len() < count {
+        let coords = Coordinates::new_random_from(&mut rng);
+        if resources.iter().all(|(existing, _)| *existing != coords) {
+            resources.push((coords, Resource::default()));
+        }
+    }
+    resources
+}
+
+/// Builds a seeded world with one colony, that spawns the given number of ants of each type immediately
+fn seeded_world(ants_per_type: u16, resource_count: usize) -> World {
+    let mut colony = Colony::default();
+    colony.set_spawn_rate(ants_per_type * ANT_TYPES.len() as u16);
+    for ant_type in &ANT_TYPES {
+        colony.set_target_population(*ant_type, ants_per_type);
+    }
+    let mut world = World::new_with_data(seeded_resources(resource_count), vec![colony]);
+    world.set_seed(SEED);
+    world
+}
+
+/// The number of tiles covering the given fraction of the world
+fn tile_count(density: f64) -> usize {
+    (density * (WORLD_WIDTH as f64) * (WORLD_HEIGHT as f64)) as usize
+}
+
+fn bench_world_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("world_update");
+    for density in &RESOURCE_DENSITIES {
+        for ants in &ANT_COUNTS {
+            let mut world = seeded_world(*ants, tile_count(*density));
+            for _ in 0..WARM_UP_TICKS {
+                world.update();
+            }
+            let id = BenchmarkId::new(format!("resources_{}", density), ants);
+            group.bench_function(id, |b| b.iter(|| world.update()));
+        }
+    }
+    group.finish();
+}
+
+fn bench_pheromone_decay(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pheromone_decay");
+    for density in &PHEROMONE_DENSITIES {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(density),
+            density,
+            |b, density| {
+                b.iter_batched(
+                    || {
+                        // A world without colonies, so only the pheromones are updated
+                        let mut world = World::new_with_data(Vec::new(), Vec::new());
+                        for (coords, _) in seeded_resources(tile_count(*density)) {
+                            world.add_pheromone(coords, Pheromone::default_exploration());
+                            world.add_pheromone(coords, Pheromone::default_resource());
+                        }
+                        world
+                    },
+                    |mut world| {
+                        world.update();
+                        world
+                    },
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_map_loading(c: &mut Criterion) {
+    let mut group = c.benchmark_group("map_loading");
+    for density in &RESOURCE_DENSITIES {
+        let resources = seeded_resources(tile_count(*density));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(density),
+            &resources,
+            |b, resources| {
+                b.iter_batched(
+                    || resources.clone(),
+                    |resources| World::new_with_data(resources, vec![Colony::default()]),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_world_update,
+    bench_pheromone_decay,
+    bench_map_loading
+);
+criterion_main!(benches);
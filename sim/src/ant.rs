@@ -1,27 +1,54 @@
-use crate::ant_settings::{ANT_BACKWARDS_CHANCE, DEFAULT_COLONY_SCOUT_SIZE, DEFAULT_COLONY_WORKER_SIZE, DEFAULT_MAX_ANT_STEPS, DEFAULT_PHEROMONE_REFRESH_AMOUNT, DEFAULT_TERRITORY_SIZE, SCOUT_RETURN_PHEROMONE_CHANCE, WORKER_PHEROMONE_CHANCE, WORLD_HEIGHT, WORLD_WIDTH, DEBUG_MODE};
+use crate::ant_settings::{DEFAULT_COLONY_SCOUT_SIZE, DEFAULT_COLONY_WORKER_SIZE, DISTANCE_METRIC};
 
-use crate::ant::AntType::Scout;
-use enum_map::EnumMap;
-use crate::pheromone::{Pheromone, PheromoneType};
-use crate::resource::Resource;
+use crate::behaviour::{Action, Behaviour, Neighbourhood};
+use crate::pheromone::PheromoneType;
+use crate::pheromone_field::PheromoneField;
+use crate::resource::ResourceKind;
+use crate::world::{AntCountMap, ResourceMap, TerrainMap};
 use crate::Coordinates;
-use rand::prelude::SliceRandom;
-use rand::thread_rng;
+use enum_map::Enum;
+use rand::RngCore;
 use std::fmt;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Debug, Display, Formatter};
 
-pub struct Ant {
-    ant_type: AntType,
-    pub position: Coordinates,
-    colony_position: Coordinates,
+/// The changes to the world requested by a single ant during a time step
+#[derive(Copy, Clone, Default)]
+pub(crate) struct AntUpdate {
+    /// The position of the resource the ant is picking up food from
+    ///
+    /// The ant only carries the food once the pickup is applied, as another ant may take the last of it first
+    pub(crate) consumed: Option<Coordinates>,
+    /// The pheromone the ant left behind, with the amount to strengthen it by
+    pub(crate) pheromone: Option<(Coordinates, PheromoneType, u16)>,
+    /// The kind of food the ant brought back to the colony, if it delivered any
+    pub(crate) delivered: Option<ResourceKind>,
+}
+
+/// The state of an ant, that changes as it travels
+#[derive(Copy, Clone, Default)]
+pub struct AntState {
     steps_on_current_journey: u16,
     is_returning_to_colony: bool,
     found_food: bool,
-    distance_from_colony: u16,
+    distance_from_colony: f64,
+    /// The kind of food being carried back to the colony
+    carrying: Option<ResourceKind>,
+    /// The number of ticks spent waiting on the current resource, to pick up food
+    pickup_progress: u16,
+    /// The number of ticks spent on the current tile, while crossing slow ground
+    move_progress: u16,
 }
 
-/// All possible directions that an ant can move in
-const MOVE_POSSIBILITIES: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+/// A single ant
+///
+/// Ants are stored by column in an [`AntStore`](crate::ant_store::AntStore), so this is a copy of one row
+#[derive(Copy, Clone)]
+pub struct Ant {
+    ant_type: AntType,
+    position: Coordinates,
+    colony_position: Coordinates,
+    state: AntState,
+}
 
 impl Ant {
     /// Creates a new ant, with the given type and position
@@ -42,111 +69,145 @@ impl Ant {
         Ant {
             ant_type,
             position,
-            is_returning_to_colony: false,
-            steps_on_current_journey: 0,
             colony_position,
-            distance_from_colony: 0,
-            found_food: false,
+            state: AntState {
+                distance_from_colony: position.distance(colony_position, DISTANCE_METRIC),
+                ..AntState::default()
+            },
+        }
+    }
+
+    /// Rebuilds an ant from the columns it is stored in
+    pub(crate) fn from_parts(
+        ant_type: AntType,
+        position: Coordinates,
+        colony_position: Coordinates,
+        state: AntState,
+    ) -> Ant {
+        Ant {
+            ant_type,
+            position,
+            colony_position,
+            state,
         }
     }
-    /// Executes the next time step for this ant
+    /// Executes the next time step for this ant, against a read-only view of the world
     /// By:
-    /// * Moving the ant
-    /// * Updating any relevant pheromones
-    /// * Consuming any available food
-    pub fn update(
+    /// * Moving the ant, using the given behaviour
+    /// * Determining any relevant pheromones
+    /// * Picking up any available food
+    ///
+    /// Changes to the world are returned, so they can be applied once every ant has moved
+    pub(crate) fn update(
         &mut self,
-        food_map: &mut [[Option<Resource>; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize],
-        pheromones_lookup: &mut Vec<(Coordinates, PheromoneType)>,
-        pheromones_map: &mut [[EnumMap<PheromoneType, Option<Pheromone>>; WORLD_HEIGHT as usize];
-            WORLD_WIDTH as usize],
-    ) {
-        self.steps_on_current_journey += 1;
-        // Consume food if it is available
-        if let Some(mut food) =
-        &food_map[self.position.x_position as usize][self.position.y_position as usize]
+        behaviour: &dyn Behaviour,
+        food_map: &ResourceMap,
+        pheromones_map: &PheromoneField,
+        ant_counts: &AntCountMap,
+        terrain: &TerrainMap,
+        rng: &mut dyn RngCore,
+    ) -> AntUpdate {
+        let mut ant_update = AntUpdate::default();
+        self.state.steps_on_current_journey += 1;
+        // Pick up food if it is available, after waiting for as long as its kind takes. Ants already carrying food
+        // pass over it without waiting
+        if let Some(resource) =
+            food_map[self.position.x_position as usize][self.position.y_position as usize]
         {
-            self.is_returning_to_colony = true;
-            self.found_food = true;
-            if food.consume().is_none() {
-                food_map[self.position.x_position as usize][self.position.y_position as usize] =
-                    None;
+            if !self.state.found_food
+                && self.state.pickup_progress < resource.kind.get_pickup_time()
+            {
+                self.state.pickup_progress += 1;
+                return ant_update;
             }
+            self.state.pickup_progress = 0;
+            ant_update.consumed = Some(self.position);
+        } else {
+            self.state.pickup_progress = 0;
         }
-        self.move_ant(pheromones_map);
-        self.update_pheromone(pheromones_lookup, pheromones_map);
-    }
-
-    /// If a pheromone of the correct type, already exists at the current position, then refreshes it
-    ///
-    /// Otherwise, creates a new default pheromone of the correct type at the current position
-    fn update_pheromone(
-        &self,
-        pheromones_lookup: &mut Vec<(Coordinates, PheromoneType)>,
-        pheromones_map: &mut [[EnumMap<PheromoneType, Option<Pheromone>>; WORLD_HEIGHT as usize];
-            WORLD_WIDTH as usize],
-    ) {
-        // Determine the pheromone type
-        let pheromone_type = if self.found_food {
-            PheromoneType::Resource
-        } else if self.ant_type == AntType::Scout && !self.is_returning_to_colony {
-            PheromoneType::Exploration
+        ant_update.delivered = self.update_journey(behaviour.get_max_steps(self.ant_type));
+        let neighbourhood =
+            Neighbourhood::new(self.position, food_map, pheromones_map, ant_counts, terrain);
+        // Slow ground holds the ant in place, until it has spent long enough on the tile
+        let move_cost = neighbourhood.get_terrain(self.position).get_move_cost();
+        let action = if self.state.move_progress + 1 < move_cost {
+            self.state.move_progress += 1;
+            Action::Stay
         } else {
-            return;
+            match behaviour.next_action(self, &neighbourhood, rng) {
+                Action::Move(position) if !neighbourhood.is_passable(position) => Action::Stay,
+                action => action,
+            }
         };
+        self.apply_action(action);
+        let deposit_amount = behaviour.get_deposit_amount(self.ant_type);
+        ant_update.pheromone = self.get_pheromone_type().map(|pheromone_type| {
+            (
+                self.position,
+                pheromone_type,
+                self.get_pheromone_amount(deposit_amount),
+            )
+        });
+        ant_update
+    }
 
-        // Attempts to refresh the pheromone
-        if let Some(pheromone) = &mut pheromones_map[self.position.x_position as usize]
-            [self.position.y_position as usize][pheromone_type]
-        {
-            pheromone.refresh(DEFAULT_PHEROMONE_REFRESH_AMOUNT);
+    /// Determines the type of pheromone the ant should leave at its current position, if any
+    fn get_pheromone_type(&self) -> Option<PheromoneType> {
+        if self.state.found_food {
+            Some(PheromoneType::Resource)
+        } else if self.ant_type == AntType::Scout && !self.state.is_returning_to_colony {
+            Some(PheromoneType::Exploration)
         } else {
-            pheromones_map[self.position.x_position as usize][self.position.y_position as usize]
-                [pheromone_type] = Some(Pheromone::default(pheromone_type));
-            pheromones_lookup.push(((self.position), pheromone_type));
+            None
         }
     }
 
-    /// Moves the ant, using one of the movement systems
+    /// The amount the ant strengthens the pheromone it leaves by, scaled by the kind of food it carries
+    fn get_pheromone_amount(&self, deposit_amount: u16) -> u16 {
+        match self.state.carrying {
+            Some(kind) => (deposit_amount as f64 * kind.get_pheromone_multiplier()) as u16,
+            None => deposit_amount,
+        }
+    }
+
+    /// Resets the journey when the ant is at the colony, or turns the ant around if the journey is too long
     ///
-    /// Is dependant on the ant type and probability of using a specified movement system, defined in [`ant_settings']
-    fn move_ant(
-        &mut self,
-        pheromones_map: &[[EnumMap<PheromoneType, Option<Pheromone>>; WORLD_HEIGHT as usize];
-            WORLD_WIDTH as usize],
-    ) {
+    /// Returns the kind of food the ant delivered to the colony, if any
+    fn update_journey(&mut self, max_steps: u16) -> Option<ResourceKind> {
+        let mut delivered = None;
         // Reset if at the colony
         if self.position == self.colony_position {
-            self.steps_on_current_journey = 0;
-            self.is_returning_to_colony = false;
-            self.found_food = false;
+            if self.state.found_food {
+                delivered = self.state.carrying;
+            }
+            self.state.steps_on_current_journey = 0;
+            self.state.is_returning_to_colony = false;
+            self.state.found_food = false;
+            self.state.carrying = None;
         }
         // If the journey has reached the max distance
-        else if self.steps_on_current_journey > DEFAULT_MAX_ANT_STEPS {
-            self.steps_on_current_journey = 0;
-            self.is_returning_to_colony = true;
+        else if self.state.steps_on_current_journey > max_steps {
+            self.state.steps_on_current_journey = 0;
+            self.state.is_returning_to_colony = true;
         }
-        // The chance of an ant following the strongest pheromone trail
-        let ant_pheromone_chance = match self.ant_type {
-            AntType::Scout => {
-                if self.is_returning_to_colony {
-                    SCOUT_RETURN_PHEROMONE_CHANCE
-                } else {
-                    // Equation = y= 1/e^(distance/DEFAULT_TERRITORY_SIZE)
-                    // Use the distance from colony, to influence the chance of taking established paths
-                    // i.e. The further from the colony, the higher chance of moving randomly
-                    1_f64 / (self.distance_from_colony as f64 / DEFAULT_TERRITORY_SIZE as f64).exp()
-                }
-            }
-            AntType::Worker => WORKER_PHEROMONE_CHANCE,
-        };
+        delivered
+    }
 
-        // Apply the correct movement system
-        let random_chance: f64 = rand::random();
-        if random_chance < ant_pheromone_chance {
-            self.move_using_pheromones(pheromones_map);
-        } else {
-            self.move_using_random();
+    /// Loads the ant with food of the given kind, turning it back towards the colony
+    pub(crate) fn pick_up(&mut self, kind: ResourceKind) {
+        self.state.is_returning_to_colony = true;
+        self.state.found_food = true;
+        self.state.carrying = Some(kind);
+    }
+
+    /// Applies the action chosen by the ants behaviour
+    fn apply_action(&mut self, action: Action) {
+        if let Action::Move(new_position) = action {
+            self.position = new_position;
+            self.state.move_progress = 0;
+            self.state.distance_from_colony = self
+                .position
+                .distance(self.colony_position, DISTANCE_METRIC);
         }
     }
 
@@ -155,108 +216,53 @@ impl Ant {
     /// i.e:
     /// * If the ant is exploring or retrieving a resource, then checks if the new position is further from the colony
     /// * Or if the ant is returning to the colony, then checks if the new position is closer to the colony,
-    fn is_correct_direction(&self, new_position: Coordinates) -> bool {
-        let new_distance = new_position.manhattan_distance(self.colony_position);
-        if self.is_returning_to_colony {
-            new_distance < self.distance_from_colony
+    pub fn is_correct_direction(&self, new_position: Coordinates) -> bool {
+        let new_distance = new_position.distance(self.colony_position, DISTANCE_METRIC);
+        if self.state.is_returning_to_colony {
+            new_distance < self.state.distance_from_colony
         } else {
-            new_distance > self.distance_from_colony
+            new_distance > self.state.distance_from_colony
         }
     }
 
-    // TODO Causes sim to freeze when edge of world is reached, as it cannot find a valid move
-    /// Moves the ant randomly in one of the possible directions given by: [`MOVE_POSSIBILITIES`]
-    ///
-    /// The chance of moving backwards, is defined in [`ant_settings']
-    fn move_using_random(&mut self) {
-        let mut allow_backwards = rand::random::<f64>() > ANT_BACKWARDS_CHANCE;
-        let mut new_position = None;
-        let mut moves = MOVE_POSSIBILITIES;
-        moves.shuffle(&mut thread_rng());
-        // Retrieves the first available valid move
-        for new_move in &moves {
-            // If a move exceeds the world boundaries, then allow backwards movement
-            if let Some(test_position) = self.position.modify(new_move.0, new_move.1) {
-                new_position = Some(test_position);
-                if allow_backwards || self.is_correct_direction(test_position) {
-                    break;
-                }
-            } else {
-                allow_backwards = true;
-            }
-        }
-        if new_position.is_none() {
-            panic!(
-                "Ant at {} cannot move, possible movements {:?}",
-                self.position, moves
-            );
-        }
-        // Apply the movement
-        let new_position = new_position.unwrap();
-        self.position = new_position;
-        self.distance_from_colony = self.position.manhattan_distance(self.colony_position);
+    pub fn get_ant_type(&self) -> AntType {
+        self.ant_type
     }
-
-    /// Moves the ant in the direction of the strongest valid pheromone     
-    ///
-    /// If there are no nearby valid pheromones then, moves in a random direction
-    fn move_using_pheromones(
-        &mut self,
-        pheromones_map: &[[EnumMap<PheromoneType, Option<Pheromone>>; WORLD_HEIGHT as usize];
-            WORLD_WIDTH as usize],
-    ) {
-        let mut strongest_pheromone = 0;
-        let mut position = Coordinates::default();
-        let mut moves = MOVE_POSSIBILITIES;
-        moves.shuffle(&mut thread_rng());
-        for move_possibility in &moves {
-            let new_position = self
-                .position
-                .safe_modify(move_possibility.0, move_possibility.1);
-            if !self.is_correct_direction(new_position) {
-                continue;
-            }
-
-            let pheromones =
-                &pheromones_map[new_position.x_position as usize][new_position.y_position as usize];
-
-            if self.ant_type == Scout {
-                if let Some(pheromone) = pheromones[PheromoneType::Exploration] {
-                    if pheromone.strength > strongest_pheromone {
-                        strongest_pheromone = pheromone.strength;
-                        position = new_position;
-                    }
-                }
-            }
-            if let Some(pheromone) = &pheromones[PheromoneType::Resource] {
-                if pheromone.strength > strongest_pheromone {
-                    strongest_pheromone = pheromone.strength;
-                    position = new_position;
-                }
-            }
-        }
-        // Fallback to random if no available pheromones
-        if strongest_pheromone == 0 {
-            self.move_using_random();
-            return;
-        }
-        if self.found_food && DEBUG_MODE {
-            println!(
-                "Moving from {} to {} is_correct {} ",
-                self.position,
-                position,
-                self.is_correct_direction(position)
-            );
-        }
-        self.position = position;
-        self.distance_from_colony = self.position.manhattan_distance(self.colony_position);
+    pub fn get_position(&self) -> Coordinates {
+        self.position
+    }
+    pub fn get_colony_position(&self) -> Coordinates {
+        self.colony_position
+    }
+    pub fn get_state(&self) -> AntState {
+        self.state
+    }
+    pub fn get_steps_on_current_journey(&self) -> u16 {
+        self.state.steps_on_current_journey
+    }
+    pub fn is_returning_to_colony(&self) -> bool {
+        self.state.is_returning_to_colony
+    }
+    pub fn has_found_food(&self) -> bool {
+        self.state.found_food
+    }
+    /// The kind of food being carried back to the colony, if any
+    pub fn get_carrying(&self) -> Option<ResourceKind> {
+        self.state.carrying
+    }
+    /// The distance from the colony, measured with [`DISTANCE_METRIC`]
+    pub fn get_distance_from_colony(&self) -> f64 {
+        self.state.distance_from_colony
     }
 }
 
+/// Every ant type, in the order they are updated
+pub const ANT_TYPES: [AntType; 2] = [AntType::Scout, AntType::Worker];
+
 /// The possible roles that an ant can take
 /// * Scout - Will explore to try and find new resources
 /// * Worker - Will move found resources to the colony
-#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Enum)]
 pub enum AntType {
     Scout,
     Worker,
@@ -272,6 +278,12 @@ impl AntType {
     }
 }
 
+impl Debug for AntType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
 impl Display for AntType {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
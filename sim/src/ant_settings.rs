@@ -1,6 +1,8 @@
 // TODO Convert to text based environment file
 // TODO Maybe allow runtime alteration?
 
+use crate::DistanceMetric;
+
 // World Settings
 /// The width of the world in "tiles"
 /// # WARNING - Changing this value can invoke SLOW release compile times
@@ -15,6 +17,8 @@ pub const WORLD_WIDTH: u16 = 16;
 /// See: https://github.com/rust-lang/rust/issues/80622
 /// Avoid values between 32 and 75
 pub const WORLD_HEIGHT: u16 = 16;
+/// The metric used by ants to measure their distance from the colony
+pub const DISTANCE_METRIC: DistanceMetric = DistanceMetric::Manhattan;
 
 // Pheromones
 /// The maximum strength that can be assigned to a pheromone
@@ -31,6 +35,16 @@ pub const SCOUT_RETURN_PHEROMONE_CHANCE: f64 = 0.9;
 pub const WORKER_PHEROMONE_CHANCE: f64 = 0.9;
 /// The probability of an ant going backwards when exploring
 pub const ANT_BACKWARDS_CHANCE: f64 = 0.1;
+/// The trail level (tau) given to tiles without a pheromone, so unmarked tiles can still be chosen
+pub const INITIAL_PHEROMONE_TAU: f64 = 0.01;
+/// How strongly scouts weight pheromone strength (alpha) when choosing a move
+pub const SCOUT_PHEROMONE_ALPHA: f64 = 1.0;
+/// How strongly scouts weight the direction of travel (beta) when choosing a move
+pub const SCOUT_HEURISTIC_BETA: f64 = 1.0;
+/// How strongly workers weight pheromone strength (alpha) when choosing a move
+pub const WORKER_PHEROMONE_ALPHA: f64 = 2.0;
+/// How strongly workers weight the direction of travel (beta) when choosing a move
+pub const WORKER_HEURISTIC_BETA: f64 = 1.0;
 
 // Colonies
 /// The amount of scouts a default colony should aim to spawn
@@ -46,9 +60,74 @@ pub const DEFAULT_MAX_ANT_STEPS: u16 = 1000;
 
 // Resource
 /// The default size of resources
-pub const DEFAULT_RESOURCE_SIZE: u8 = 20;
+pub const DEFAULT_RESOURCE_SIZE: u32 = 20;
 /// The amount of individual resource locations to spawn
 pub const DEFAULT_RESOURCE_COUNT: u8 = 5;
 
+// Resource kinds
+/// The amount added to a colony's stock, for each unit of sugar delivered
+pub const SUGAR_NUTRITIONAL_VALUE: u16 = 1;
+/// The amount of units in a new sugar resource
+pub const SUGAR_RESOURCE_SIZE: u32 = DEFAULT_RESOURCE_SIZE;
+/// The number of ticks an ant waits on a sugar resource, before picking up a unit
+pub const SUGAR_PICKUP_TIME: u16 = 0;
+/// How much stronger the trail left by an ant carrying sugar is, than a default trail
+pub const SUGAR_PHEROMONE_MULTIPLIER: f64 = 1.0;
+/// The amount added to a colony's stock, for each unit of protein delivered
+pub const PROTEIN_NUTRITIONAL_VALUE: u16 = 3;
+/// The amount of units in a new protein resource
+pub const PROTEIN_RESOURCE_SIZE: u32 = 10;
+/// The number of ticks an ant waits on a protein resource, before picking up a unit
+pub const PROTEIN_PICKUP_TIME: u16 = 3;
+/// How much stronger the trail left by an ant carrying protein is, than a default trail
+pub const PROTEIN_PHEROMONE_MULTIPLIER: f64 = 1.5;
+/// The amount added to a colony's stock, for each unit of water delivered
+pub const WATER_NUTRITIONAL_VALUE: u16 = 1;
+/// The amount of units in a new water resource
+pub const WATER_RESOURCE_SIZE: u32 = 40;
+/// The number of ticks an ant waits on a water resource, before picking up a unit
+pub const WATER_PICKUP_TIME: u16 = 1;
+/// How much stronger the trail left by an ant carrying water is, than a default trail
+pub const WATER_PHEROMONE_MULTIPLIER: f64 = 0.5;
+
+// Terrain
+/// The number of ticks an ant spends on slow ground, before it can move off it
+pub const SLOW_TERRAIN_MOVE_COST: u16 = 3;
+
+// World generation
+/// How quickly the generated terrain changes between tiles, with smaller values giving larger features
+pub const GENERATOR_NOISE_SCALE: f64 = 0.18;
+/// The noise value above which a generated tile is a wall
+pub const GENERATOR_WALL_THRESHOLD: f64 = 0.25;
+/// The noise value above which a generated tile is slow ground
+pub const GENERATOR_SLOW_THRESHOLD: f64 = 0.1;
+/// The moisture value above which resources are generated as water
+pub const GENERATOR_WET_THRESHOLD: f64 = 0.2;
+/// The smallest share of the world that must be reachable, before a generated layout is accepted
+pub const GENERATOR_MINIMUM_REACHABLE_FRACTION: f64 = 0.5;
+/// The number of layouts tried, before the generator gives up on walls altogether
+pub const GENERATOR_ATTEMPTS: u8 = 8;
+
+// Evolution
+/// The number of candidate parameter sets in each generation
+pub const EVOLUTION_POPULATION_SIZE: usize = 20;
+/// The number of generations evolved
+pub const EVOLUTION_GENERATIONS: u32 = 10;
+/// The number of seeded worlds each candidate is evaluated in
+pub const EVOLUTION_SEEDS: u64 = 3;
+/// The number of ticks each evaluation runs for
+pub const EVOLUTION_TICKS: u64 = 500;
+/// The number of best candidates carried over unchanged to the next generation
+pub const EVOLUTION_ELITES: usize = 2;
+/// The number of candidates compared when choosing each parent
+pub const EVOLUTION_TOURNAMENT_SIZE: usize = 3;
+/// The probability of each gene mutating in a child
+pub const EVOLUTION_MUTATION_CHANCE: f64 = 0.2;
+/// The largest change made by a mutation, as a share of the gene's range
+pub const EVOLUTION_MUTATION_STRENGTH: f64 = 0.2;
+
+// Replay
+/// How many frames are recorded between each full snapshot of the world, in a replay
+pub const DEFAULT_REPLAY_KEYFRAME_INTERVAL: u32 = 100;
 
 pub const DEBUG_MODE: bool = false;
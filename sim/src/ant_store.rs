@@ -0,0 +1,223 @@
+use crate::ant::{Ant, AntState, AntType, AntUpdate};
+use crate::resource::ResourceKind;
+use crate::Coordinates;
+use enum_map::EnumMap;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::fmt;
+use std::fmt::{Debug, Display, Formatter};
+
+/// An identifier for an ant, that stays the same for the lifetime of the ant
+///
+/// Identifiers are unique within a colony, and are never reused
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct AntId(pub(crate) u64);
+
+impl AntId {
+    pub fn get_id(&self) -> u64 {
+        self.0
+    }
+}
+
+impl Display for AntId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+impl Debug for AntId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+/// A container for the ants of a colony, stored as a structure of arrays
+///
+/// Each field of an ant is stored in its own column, so passes over the whole population (such as counting the ants
+/// on each tile) only touch the data they need. Ants are kept in the order they were added, so their ids are sorted
+pub struct AntStore {
+    /// The position all the ants return to
+    colony_position: Coordinates,
+    ids: Vec<AntId>,
+    ant_types: Vec<AntType>,
+    positions: Vec<Coordinates>,
+    states: Vec<AntState>,
+    /// The number of ants of each type
+    type_counts: EnumMap<AntType, usize>,
+    /// The identifier given to the next ant
+    next_id: u64,
+}
+
+impl AntStore {
+    /// Creates an empty store, for ants belonging to the colony at the given position
+    pub fn new(colony_position: Coordinates) -> AntStore {
+        AntStore {
+            colony_position,
+            ids: Vec::new(),
+            ant_types: Vec::new(),
+            positions: Vec::new(),
+            states: Vec::new(),
+            type_counts: EnumMap::default(),
+            next_id: 0,
+        }
+    }
+
+    /// Adds a new ant of the given type at the given position, and returns its identifier
+    ///
+    /// # Example
+    /// ```
+    /// # use sim::ant::AntType;
+    /// # use sim::ant_store::AntStore;
+    /// # use sim::Coordinates;
+    /// let colony_position = Coordinates::new(5, 5).unwrap();
+    /// let mut store = AntStore::new(colony_position);
+    ///
+    /// let first = store.push(AntType::Scout, colony_position);
+    /// let second = store.push(AntType::Worker, colony_position);
+    ///
+    /// assert_ne!(first, second);
+    /// assert_eq!(store.len(), 2);
+    /// assert_eq!(store.count(AntType::Scout), 1);
+    /// ```
+    pub fn push(&mut self, ant_type: AntType, position: Coordinates) -> AntId {
+        let id = AntId(self.next_id);
+        self.next_id += 1;
+        self.ids.push(id);
+        self.ant_types.push(ant_type);
+        self.positions.push(position);
+        self.states.push(AntState::default());
+        self.type_counts[ant_type] += 1;
+        id
+    }
+
+    /// Removes the ant with the given identifier, returning it if it existed
+    ///
+    /// The identifiers of the remaining ants are unchanged
+    /// # Example
+    /// ```
+    /// # use sim::ant::AntType;
+    /// # use sim::ant_store::AntStore;
+    /// # use sim::Coordinates;
+    /// let colony_position = Coordinates::new(5, 5).unwrap();
+    /// let mut store = AntStore::new(colony_position);
+    /// let first = store.push(AntType::Scout, colony_position);
+    /// let second = store.push(AntType::Worker, colony_position);
+    ///
+    /// assert!(store.remove(first).is_some());
+    /// assert!(store.remove(first).is_none());
+    /// assert!(store.get(second).is_some());
+    /// ```
+    pub fn remove(&mut self, id: AntId) -> Option<Ant> {
+        let index = self.index_of(id)?;
+        let ant = self.get_by_index(index);
+        self.ids.remove(index);
+        self.ant_types.remove(index);
+        self.positions.remove(index);
+        self.states.remove(index);
+        self.type_counts[ant.get_ant_type()] -= 1;
+        Some(ant)
+    }
+
+    /// Loads the ant with the given identifier, if it exists, with food of the given kind
+    pub(crate) fn pick_up(&mut self, id: AntId, kind: ResourceKind) {
+        if let Some(index) = self.index_of(id) {
+            let mut ant = self.get_by_index(index);
+            ant.pick_up(kind);
+            self.states[index] = ant.get_state();
+        }
+    }
+
+    /// Returns a copy of the ant with the given identifier
+    pub fn get(&self, id: AntId) -> Option<Ant> {
+        self.index_of(id).map(|index| self.get_by_index(index))
+    }
+
+    /// The number of ants in the store
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Returns true if there are no ants in the store
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// The number of ants of the given type
+    pub fn count(&self, ant_type: AntType) -> usize {
+        self.type_counts[ant_type]
+    }
+
+    /// The identifiers of every ant, in ascending order
+    pub fn ids(&self) -> &[AntId] {
+        &self.ids
+    }
+
+    /// The type of every ant, in the same order as [`AntStore::ids`]
+    pub fn ant_types(&self) -> &[AntType] {
+        &self.ant_types
+    }
+
+    /// The position of every ant, in the same order as [`AntStore::ids`]
+    pub fn positions(&self) -> &[Coordinates] {
+        &self.positions
+    }
+
+    /// Returns an iterator over a copy of every ant, with its identifier
+    pub fn iter(&self) -> impl Iterator<Item = (AntId, Ant)> + '_ {
+        (0..self.len()).map(move |index| (self.ids[index], self.get_by_index(index)))
+    }
+
+    /// Applies the updater to every ant, and writes back any changes to its position and state
+    ///
+    /// With the `parallel` feature the ants are updated in parallel, but the results are always returned in the
+    /// order of the ants, with their identifiers
+    pub(crate) fn update<F>(&mut self, updater: F) -> Vec<(AntId, AntUpdate)>
+    where
+        F: Fn(AntId, &mut Ant) -> AntUpdate + Send + Sync,
+    {
+        let colony_position = self.colony_position;
+        let update_ant =
+            |id: &AntId, ant_type: &AntType, position: &mut Coordinates, state: &mut AntState| {
+                let mut ant = Ant::from_parts(*ant_type, *position, colony_position, *state);
+                let ant_update = updater(*id, &mut ant);
+                *position = ant.get_position();
+                *state = ant.get_state();
+                (*id, ant_update)
+            };
+
+        #[cfg(feature = "parallel")]
+        let updates = (
+            self.ids.par_iter(),
+            self.ant_types.par_iter(),
+            self.positions.par_iter_mut(),
+            self.states.par_iter_mut(),
+        )
+            .into_par_iter()
+            .map(|(id, ant_type, position, state)| update_ant(id, ant_type, position, state))
+            .collect();
+        #[cfg(not(feature = "parallel"))]
+        let updates = self
+            .ids
+            .iter()
+            .zip(&self.ant_types)
+            .zip(self.positions.iter_mut().zip(self.states.iter_mut()))
+            .map(|((id, ant_type), (position, state))| update_ant(id, ant_type, position, state))
+            .collect();
+        updates
+    }
+
+    /// Finds the index of the ant with the given identifier
+    fn index_of(&self, id: AntId) -> Option<usize> {
+        self.ids.binary_search(&id).ok()
+    }
+
+    /// Rebuilds the ant at the given index from its columns
+    fn get_by_index(&self, index: usize) -> Ant {
+        Ant::from_parts(
+            self.ant_types[index],
+            self.positions[index],
+            self.colony_position,
+            self.states[index],
+        )
+    }
+}
@@ -0,0 +1,496 @@
+use crate::ant::{Ant, AntType, ANT_TYPES};
+use crate::ant_settings::{
+    ANT_BACKWARDS_CHANCE, DEBUG_MODE, DEFAULT_MAX_ANT_STEPS, DEFAULT_PHEROMONE_REFRESH_AMOUNT,
+    DEFAULT_TERRITORY_SIZE, INITIAL_PHEROMONE_TAU, MAXIMUM_PHEROMONE_STRENGTH,
+    SCOUT_HEURISTIC_BETA, SCOUT_PHEROMONE_ALPHA, SCOUT_RETURN_PHEROMONE_CHANCE,
+    WORKER_HEURISTIC_BETA, WORKER_PHEROMONE_ALPHA, WORKER_PHEROMONE_CHANCE,
+};
+use crate::pheromone::{Pheromone, PheromoneType};
+use crate::pheromone_field::PheromoneField;
+use crate::resource::Resource;
+use crate::terrain::Terrain;
+use crate::world::{AntCountMap, ResourceMap, TerrainMap};
+use crate::Coordinates;
+use enum_map::EnumMap;
+use rand::prelude::SliceRandom;
+use rand::{Rng, RngCore};
+
+/// All possible directions that an ant can move in
+pub const MOVE_POSSIBILITIES: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// The decision an ant makes for a single time step
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// Move to the given tile
+    Move(Coordinates),
+    /// Remain on the current tile
+    Stay,
+}
+
+/// A read-only view of the world surrounding an ant, used by a [`Behaviour`] to make its decision
+pub struct Neighbourhood<'a> {
+    position: Coordinates,
+    resources: &'a ResourceMap,
+    pheromones: &'a PheromoneField,
+    ant_counts: &'a AntCountMap,
+    terrain: &'a TerrainMap,
+}
+
+impl<'a> Neighbourhood<'a> {
+    /// Creates a view of the given maps, centred on the given position
+    ///
+    /// The world builds one for every ant, but one can be built by hand to drive a [`Behaviour`] directly
+    pub fn new(
+        position: Coordinates,
+        resources: &'a ResourceMap,
+        pheromones: &'a PheromoneField,
+        ant_counts: &'a AntCountMap,
+        terrain: &'a TerrainMap,
+    ) -> Neighbourhood<'a> {
+        Neighbourhood {
+            position,
+            resources,
+            pheromones,
+            ant_counts,
+            terrain,
+        }
+    }
+
+    /// The position the neighbourhood is centred on
+    pub fn get_position(&self) -> Coordinates {
+        self.position
+    }
+
+    /// Returns every tile that can be reached in one move, in the order of [`MOVE_POSSIBILITIES`]
+    pub fn get_adjacent_tiles(&self) -> Vec<Coordinates> {
+        MOVE_POSSIBILITIES
+            .iter()
+            .filter_map(|(x, y)| self.position.modify(*x, *y))
+            .filter(|coords| self.is_passable(*coords))
+            .collect()
+    }
+
+    /// Returns true if an ant can stand on the given tile
+    pub fn is_passable(&self, coords: Coordinates) -> bool {
+        self.get_terrain(coords).is_passable()
+    }
+
+    /// Returns the terrain of the given tile
+    pub fn get_terrain(&self, coords: Coordinates) -> Terrain {
+        self.terrain[coords.get_x_position_usize()][coords.get_y_position_usize()]
+    }
+
+    /// Returns the pheromone of the given type at the given tile, if one exists
+    pub fn get_pheromone(
+        &self,
+        coords: Coordinates,
+        pheromone_type: PheromoneType,
+    ) -> Option<&Pheromone> {
+        self.pheromones.get(coords, pheromone_type)
+    }
+
+    /// Returns the resource at the given tile, if one exists
+    pub fn get_resource(&self, coords: Coordinates) -> Option<&Resource> {
+        self.resources[coords.get_x_position_usize()][coords.get_y_position_usize()].as_ref()
+    }
+
+    /// Returns the number of ants (from every colony) on the given tile, at the start of the time step
+    pub fn get_ant_count(&self, coords: Coordinates) -> u32 {
+        self.ant_counts[coords.get_x_position_usize()][coords.get_y_position_usize()]
+    }
+}
+
+/// The decision logic used to move ants
+///
+/// Implementations are given a read-only view of the ant and its surroundings, and return the action to take.
+/// Any randomness should be drawn from the supplied `rng`
+pub trait Behaviour: Send + Sync {
+    /// Decides the action the given ant will take this time step
+    fn next_action(
+        &self,
+        ant: &Ant,
+        neighbourhood: &Neighbourhood,
+        rng: &mut dyn RngCore,
+    ) -> Action;
+
+    /// The number of steps an ant of the given type takes on a journey, before turning back to the nest
+    fn get_max_steps(&self, _ant_type: AntType) -> u16 {
+        DEFAULT_MAX_ANT_STEPS
+    }
+
+    /// The amount an ant of the given type strengthens the pheromone it leaves by, before scaling by the food carried
+    fn get_deposit_amount(&self, _ant_type: AntType) -> u16 {
+        DEFAULT_PHEROMONE_REFRESH_AMOUNT
+    }
+}
+
+/// The names of every parameter of the default behaviour
+///
+/// Names starting with `SCOUT_` or `WORKER_` belong to that ant type, and those starting with `ANT_` set the parameter
+/// for every ant type. The rest are shared by every ant type
+pub const PARAMETER_NAMES: [&str; 21] = [
+    "DEFAULT_TERRITORY_SIZE",
+    "INITIAL_PHEROMONE_TAU",
+    "SCOUT_RETURN_PHEROMONE_CHANCE",
+    "ANT_PHEROMONE_CHANCE",
+    "ANT_BACKWARDS_CHANCE",
+    "ANT_PHEROMONE_ALPHA",
+    "ANT_HEURISTIC_BETA",
+    "ANT_MAX_STEPS",
+    "ANT_DEPOSIT_AMOUNT",
+    "SCOUT_PHEROMONE_CHANCE",
+    "SCOUT_BACKWARDS_CHANCE",
+    "SCOUT_PHEROMONE_ALPHA",
+    "SCOUT_HEURISTIC_BETA",
+    "SCOUT_MAX_STEPS",
+    "SCOUT_DEPOSIT_AMOUNT",
+    "WORKER_PHEROMONE_CHANCE",
+    "WORKER_BACKWARDS_CHANCE",
+    "WORKER_PHEROMONE_ALPHA",
+    "WORKER_HEURISTIC_BETA",
+    "WORKER_MAX_STEPS",
+    "WORKER_DEPOSIT_AMOUNT",
+];
+
+/// The tunable values used by the default behaviour for a single ant type
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AntTypeParameters {
+    /// The probability of following pheromones, which for scouts only applies while returning to the nest
+    pub pheromone_chance: f64,
+    /// The probability of going backwards when exploring
+    pub backwards_chance: f64,
+    /// How strongly pheromone strength (alpha) is weighted when choosing a move
+    pub pheromone_alpha: f64,
+    /// How strongly the direction of travel (beta) is weighted when choosing a move
+    pub heuristic_beta: f64,
+    /// The number of steps taken on a journey, before turning back to the nest
+    pub max_steps: u16,
+    /// The amount the pheromone left behind is strengthened by, before scaling by the food carried
+    pub deposit_amount: u16,
+}
+
+impl AntTypeParameters {
+    /// Creates the parameters for the given ant type, from [`ant_settings`]
+    pub fn from_settings(ant_type: AntType) -> AntTypeParameters {
+        AntTypeParameters {
+            pheromone_chance: match ant_type {
+                AntType::Scout => SCOUT_RETURN_PHEROMONE_CHANCE,
+                AntType::Worker => WORKER_PHEROMONE_CHANCE,
+            },
+            backwards_chance: ANT_BACKWARDS_CHANCE,
+            pheromone_alpha: match ant_type {
+                AntType::Scout => SCOUT_PHEROMONE_ALPHA,
+                AntType::Worker => WORKER_PHEROMONE_ALPHA,
+            },
+            heuristic_beta: match ant_type {
+                AntType::Scout => SCOUT_HEURISTIC_BETA,
+                AntType::Worker => WORKER_HEURISTIC_BETA,
+            },
+            max_steps: DEFAULT_MAX_ANT_STEPS,
+            deposit_amount: DEFAULT_PHEROMONE_REFRESH_AMOUNT,
+        }
+    }
+
+    /// Returns the value of the field with the given name, the part of a parameter name after the ant type
+    fn get(&self, field: &str) -> Option<f64> {
+        match field {
+            "PHEROMONE_CHANCE" => Some(self.pheromone_chance),
+            "BACKWARDS_CHANCE" => Some(self.backwards_chance),
+            "PHEROMONE_ALPHA" => Some(self.pheromone_alpha),
+            "HEURISTIC_BETA" => Some(self.heuristic_beta),
+            "MAX_STEPS" => Some(self.max_steps as f64),
+            "DEPOSIT_AMOUNT" => Some(self.deposit_amount as f64),
+            _ => None,
+        }
+    }
+
+    /// Replaces the value of the field with the given name, rounding whole number fields
+    fn set(&mut self, field: &str, value: f64) -> bool {
+        match field {
+            "PHEROMONE_CHANCE" => self.pheromone_chance = value,
+            "BACKWARDS_CHANCE" => self.backwards_chance = value,
+            "PHEROMONE_ALPHA" => self.pheromone_alpha = value,
+            "HEURISTIC_BETA" => self.heuristic_beta = value,
+            "MAX_STEPS" => self.max_steps = value.round() as u16,
+            "DEPOSIT_AMOUNT" => self.deposit_amount = value.round() as u16,
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// The tunable values used by the default behaviour, which default to those in [`ant_settings`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AntParameters {
+    /// The distance from the colony over which exploring scouts stop following pheromones
+    pub territory_size: f64,
+    /// The trail level (tau) given to tiles without a pheromone
+    pub initial_pheromone_tau: f64,
+    /// The parameters for each ant type
+    pub ant_types: EnumMap<AntType, AntTypeParameters>,
+}
+
+impl Default for AntParameters {
+    fn default() -> Self {
+        AntParameters {
+            territory_size: DEFAULT_TERRITORY_SIZE as f64,
+            initial_pheromone_tau: INITIAL_PHEROMONE_TAU,
+            ant_types: EnumMap::from(AntTypeParameters::from_settings),
+        }
+    }
+}
+
+impl AntParameters {
+    /// Returns the value of the parameter with the given name (one of [`PARAMETER_NAMES`]), if it exists
+    ///
+    /// A name starting with `ANT_` only has a value while every ant type shares it
+    pub fn get(&self, name: &str) -> Option<f64> {
+        match name {
+            "DEFAULT_TERRITORY_SIZE" => Some(self.territory_size),
+            "INITIAL_PHEROMONE_TAU" => Some(self.initial_pheromone_tau),
+            "SCOUT_RETURN_PHEROMONE_CHANCE" => {
+                Some(self.ant_types[AntType::Scout].pheromone_chance)
+            }
+            _ => {
+                let (ant_types, field) = split_parameter_name(name)?;
+                let mut values = ant_types
+                    .iter()
+                    .map(|ant_type| self.ant_types[*ant_type].get(field));
+                let first = values.next()??;
+                if values.all(|value| value == Some(first)) {
+                    Some(first)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Replaces the value of the parameter with the given name (one of [`PARAMETER_NAMES`])
+    ///
+    /// Returns false if there is no parameter with the given name
+    /// # Example
+    /// ```
+    /// # use sim::ant::AntType;
+    /// # use sim::behaviour::AntParameters;
+    /// let mut parameters = AntParameters::default();
+    /// assert!(parameters.set("ANT_BACKWARDS_CHANCE", 0.25));
+    /// assert_eq!(parameters.ant_types[AntType::Worker].backwards_chance, 0.25);
+    /// assert_eq!(parameters.get("ANT_BACKWARDS_CHANCE"), Some(0.25));
+    ///
+    /// assert!(parameters.set("SCOUT_MAX_STEPS", 250.0));
+    /// assert_eq!(parameters.ant_types[AntType::Scout].max_steps, 250);
+    /// assert_eq!(parameters.get("ANT_MAX_STEPS"), None);
+    ///
+    /// assert!(!parameters.set("NOT_A_PARAMETER", 1.0));
+    /// ```
+    pub fn set(&mut self, name: &str, value: f64) -> bool {
+        match name {
+            "DEFAULT_TERRITORY_SIZE" => self.territory_size = value,
+            "INITIAL_PHEROMONE_TAU" => self.initial_pheromone_tau = value,
+            "SCOUT_RETURN_PHEROMONE_CHANCE" => {
+                self.ant_types[AntType::Scout].pheromone_chance = value
+            }
+            _ => {
+                return match split_parameter_name(name) {
+                    Some((ant_types, field)) => ant_types
+                        .iter()
+                        .all(|ant_type| self.ant_types[*ant_type].set(field, value)),
+                    None => false,
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Splits a per ant type parameter name into the ant types it applies to, and the name of the field
+fn split_parameter_name(name: &str) -> Option<(&'static [AntType], &str)> {
+    if let Some(field) = name.strip_prefix("SCOUT_") {
+        Some((&[AntType::Scout], field))
+    } else if let Some(field) = name.strip_prefix("WORKER_") {
+        Some((&[AntType::Worker], field))
+    } else {
+        name.strip_prefix("ANT_")
+            .map(|field| (&ANT_TYPES[..], field))
+    }
+}
+
+/// The default foraging strategy
+///
+/// Ants either follow pheromone trails (choosing between them probabilistically), or move randomly,
+/// with the probabilities given by its parameters
+#[derive(Copy, Clone, Default)]
+pub struct DefaultBehaviour {
+    parameters: AntParameters,
+}
+
+impl DefaultBehaviour {
+    /// Creates the default behaviour, using the given parameters instead of those in [`ant_settings`]
+    pub fn new(parameters: AntParameters) -> DefaultBehaviour {
+        DefaultBehaviour { parameters }
+    }
+
+    pub fn get_parameters(&self) -> AntParameters {
+        self.parameters
+    }
+}
+
+impl Behaviour for DefaultBehaviour {
+    fn next_action(
+        &self,
+        ant: &Ant,
+        neighbourhood: &Neighbourhood,
+        rng: &mut dyn RngCore,
+    ) -> Action {
+        let parameters = &self.parameters.ant_types[ant.get_ant_type()];
+        // The chance of an ant following pheromone trails
+        let ant_pheromone_chance = match ant.get_ant_type() {
+            AntType::Scout => {
+                if ant.is_returning_to_colony() {
+                    parameters.pheromone_chance
+                } else {
+                    // Equation = y= 1/e^(distance/DEFAULT_TERRITORY_SIZE)
+                    // Use the distance from colony, to influence the chance of taking established paths
+                    // i.e. The further from the colony, the higher chance of moving randomly
+                    1_f64 / (ant.get_distance_from_colony() / self.parameters.territory_size).exp()
+                }
+            }
+            AntType::Worker => parameters.pheromone_chance,
+        };
+
+        // Apply the correct movement system
+        let random_chance: f64 = rng.gen();
+        let new_position = if random_chance < ant_pheromone_chance {
+            move_using_pheromones(ant, neighbourhood, &self.parameters, rng)
+        } else {
+            move_using_random(ant, neighbourhood, parameters, rng)
+        };
+        match new_position {
+            Some(position) => Action::Move(position),
+            None => Action::Stay,
+        }
+    }
+
+    fn get_max_steps(&self, ant_type: AntType) -> u16 {
+        self.parameters.ant_types[ant_type].max_steps
+    }
+
+    fn get_deposit_amount(&self, ant_type: AntType) -> u16 {
+        self.parameters.ant_types[ant_type].deposit_amount
+    }
+}
+
+/// Picks a random tile from the possible directions given by: [`MOVE_POSSIBILITIES`]
+///
+/// The chance of moving backwards, is given by the parameters
+fn move_using_random(
+    ant: &Ant,
+    neighbourhood: &Neighbourhood,
+    parameters: &AntTypeParameters,
+    rng: &mut dyn RngCore,
+) -> Option<Coordinates> {
+    let allow_backwards = rng.gen::<f64>() > parameters.backwards_chance;
+    let mut moves = neighbourhood.get_adjacent_tiles();
+    // If a move exceeds the world boundaries, then allow backwards movement
+    let allow_backwards = allow_backwards || moves.len() < MOVE_POSSIBILITIES.len();
+    moves.shuffle(rng);
+    // Retrieves the first available valid move
+    let mut new_position = None;
+    for test_position in moves {
+        new_position = Some(test_position);
+        if allow_backwards || ant.is_correct_direction(test_position) {
+            break;
+        }
+    }
+    new_position
+}
+
+/// The trail level (tau) of the given tile, from the pheromones the ant follows
+///
+/// Returns None if there are no pheromones the ant can follow on the tile
+fn pheromone_level(ant: &Ant, neighbourhood: &Neighbourhood, coords: Coordinates) -> Option<u16> {
+    let mut strongest = neighbourhood
+        .get_pheromone(coords, PheromoneType::Resource)
+        .map(|pheromone| pheromone.strength);
+    if ant.get_ant_type() == AntType::Scout {
+        if let Some(pheromone) = neighbourhood.get_pheromone(coords, PheromoneType::Exploration) {
+            strongest = strongest.max(Some(pheromone.strength));
+        }
+    }
+    strongest
+}
+
+/// The heuristic desirability (eta) of moving to the given tile
+///
+/// Moves in the correct direction for the ants journey are preferred, and other moves are weighted by the chance of
+/// going backwards
+fn heuristic_level(ant: &Ant, coords: Coordinates, parameters: &AntTypeParameters) -> f64 {
+    if ant.is_correct_direction(coords) {
+        1.0
+    } else {
+        parameters.backwards_chance
+    }
+}
+
+/// Picks an adjacent tile, weighted by the pheromones on it, as in Ant System
+///
+/// Each tile is chosen with probability proportional to `tau^alpha * eta^beta`, where:
+/// * `tau` is the strength of the strongest followable pheromone (plus the initial trail level)
+/// * `eta` is the heuristic desirability of the move
+/// * `alpha` and `beta` are given by the parameters for the ant type
+///
+/// If there are no nearby valid pheromones then, picks a random direction
+fn move_using_pheromones(
+    ant: &Ant,
+    neighbourhood: &Neighbourhood,
+    parameters: &AntParameters,
+    rng: &mut dyn RngCore,
+) -> Option<Coordinates> {
+    let type_parameters = &parameters.ant_types[ant.get_ant_type()];
+    let alpha = type_parameters.pheromone_alpha;
+    let beta = type_parameters.heuristic_beta;
+
+    let mut found_pheromone = false;
+    let mut total_weight = 0.0;
+    let mut candidates = Vec::with_capacity(MOVE_POSSIBILITIES.len());
+    for new_position in neighbourhood.get_adjacent_tiles() {
+        let strength = pheromone_level(ant, neighbourhood, new_position);
+        found_pheromone |= strength.is_some();
+        let tau = strength.unwrap_or(0) as f64 / MAXIMUM_PHEROMONE_STRENGTH as f64
+            + parameters.initial_pheromone_tau;
+        let eta = heuristic_level(ant, new_position, type_parameters);
+        let weight = tau.powf(alpha) * eta.powf(beta);
+        total_weight += weight;
+        candidates.push((new_position, weight));
+    }
+    // Fallback to random if no available pheromones
+    if !found_pheromone || total_weight <= 0.0 {
+        return move_using_random(ant, neighbourhood, type_parameters, rng);
+    }
+
+    // Roulette wheel selection
+    let mut remaining = rng.gen::<f64>() * total_weight;
+    let mut position = None;
+    for (new_position, weight) in candidates {
+        if weight <= 0.0 {
+            continue;
+        }
+        position = Some(new_position);
+        if remaining < weight {
+            break;
+        }
+        remaining -= weight;
+    }
+    if ant.has_found_food() && DEBUG_MODE {
+        if let Some(position) = position {
+            println!(
+                "Moving from {} to {} is_correct {} ",
+                ant.get_position(),
+                position,
+                ant.is_correct_direction(position)
+            );
+        }
+    }
+    position
+}
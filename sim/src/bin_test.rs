@@ -1,5 +1,321 @@
-mod world;
+//! Runs the simulation without a window, for machines with no display
+//!
+//! * `--ticks <n>` - How many ticks to run for
+//! * `--seed <seed>` - The seed to start a new world with
+//! * `--map <path>` - A map file to start from, instead of a new world
+//! * `--generate <seed>` - The seed to procedurally generate a world with, instead of a new world
+//! * `--record <path>` - A replay file to record every tick to, which the graphics frontend can play back
+//! * `--every <n>` - How many ticks apart each exported frame is
+//! * `--tile-size <n>` - The size of each tile in exported frames, in pixels, up to 64
+//! * `--png-dir <dir>` - A directory to write every exported frame to, as `frame_<tick>.png`
+//! * `--gif <path>` - A file to write every exported frame to, as an animated GIF
+//! * `--sweep <NAME=start..end:step | NAME=a,b,...>` - Runs a parameter sweep instead, over every combination of
+//!   the given values (repeat for each parameter), starting from `--seed` (or zero) for `--seeds` seeds per point
+//! * `--seeds <n>` - How many seeds are run at each point of a sweep, or for each candidate of an evolution
+//! * `--csv <path>` - A file to write the results of a sweep to, instead of printing them
+//! * `--evolve <generations>` - Evolves the ant parameters instead, for the given number of generations, evaluating
+//!   each candidate for `--ticks` ticks over `--seeds` seeds from `--seed` (or zero)
+//! * `--population <n>` - How many candidates are in each generation of an evolution
+//! * `--fitness <tick | ant>` - Whether an evolution favours food delivered per tick, or per ant per tick
+//!
+//! Exporting frames needs the `export` feature. Sweeps and evolutions run in parallel either way, on rayon's thread
+//! pool with the `parallel` feature, or a thread per core without it
+use sim::ant::ANT_TYPES;
+use sim::ant_settings::DEFAULT_REPLAY_KEYFRAME_INTERVAL;
+use sim::event::Event;
+use sim::evolution;
+use sim::evolution::{EvolutionSettings, Fitness};
+use sim::generation;
+use sim::generation::GeneratorSettings;
+use sim::map;
+use sim::replay::Recorder;
+use sim::sweep::{Sweep, SweepAxis};
+use sim::world::World;
+use std::fs::File;
+use std::io;
+use std::io::{BufReader, BufWriter};
+use std::process;
+
+/// The number of ticks run, if none is given
+const DEFAULT_TICKS: u64 = 1000;
+/// The number of seeds run at each point of a sweep, if none is given
+const DEFAULT_SEEDS: u64 = 5;
+/// The number of ticks between exported frames, if none is given
+const DEFAULT_EVERY: u64 = 10;
+/// The size of each tile in exported frames, if none is given
+#[cfg(feature = "export")]
+const DEFAULT_TILE_SIZE: u32 = 8;
+/// The largest size of each tile in exported frames, which keeps every frame well inside the limits of a GIF
+const MAXIMUM_TILE_SIZE: u64 = 64;
+/// How long each frame of an animated GIF is shown for, in hundredths of a second
+#[cfg(feature = "export")]
+const GIF_FRAME_DELAY: u16 = 10;
+
+/// The options given on the command line
+#[derive(Default)]
+struct Options {
+    ticks: Option<u64>,
+    seed: Option<u64>,
+    map: Option<String>,
+    generate: Option<u64>,
+    record: Option<String>,
+    every: Option<u64>,
+    tile_size: Option<u32>,
+    png_dir: Option<String>,
+    gif: Option<String>,
+    sweep: Vec<SweepAxis>,
+    seeds: Option<u64>,
+    csv: Option<String>,
+    evolve: Option<u32>,
+    population: Option<usize>,
+    fitness: Option<Fitness>,
+}
+
 fn main() {
-    println!("Hello world");
-    //World::new();
+    let options = parse_options().unwrap_or_else(|message| exit(&message));
+    if !options.sweep.is_empty() {
+        run_sweep(options);
+        return;
+    }
+    if options.evolve.is_some() {
+        run_evolution(options);
+        return;
+    }
+    let mut world = match (&options.map, options.generate, options.seed) {
+        (Some(path), _, _) => File::open(path)
+            .and_then(|file| map::load(BufReader::new(file)))
+            .unwrap_or_else(|e| exit(&format!("Could not load map: {}", e))),
+        (None, Some(seed), _) => generation::generate(seed, &GeneratorSettings::default()),
+        (None, None, Some(seed)) => World::new_with_seed(seed),
+        (None, None, None) => World::new(),
+    };
+    let ticks = options.ticks.unwrap_or(DEFAULT_TICKS);
+    let every = options.every.unwrap_or(DEFAULT_EVERY).max(1);
+    let mut exporter = Exporter::new(&options);
+    let mut recorder = options.record.as_ref().map(|path| {
+        File::create(path)
+            .and_then(|file| {
+                Recorder::new(
+                    &world,
+                    BufWriter::new(file),
+                    DEFAULT_REPLAY_KEYFRAME_INTERVAL,
+                )
+            })
+            .unwrap_or_else(|e| exit(&format!("Could not create {}: {}", path, e)))
+    });
+
+    let mut delivered = 0;
+    exporter.export(&world);
+    for _ in 0..ticks {
+        world.update();
+        delivered += world
+            .get_events()
+            .iter()
+            .filter(|event| matches!(event, Event::FoodDelivered { .. }))
+            .count();
+        if world.get_tick().is_multiple_of(every) {
+            exporter.export(&world);
+        }
+        if let Some(recorder) = &mut recorder {
+            recorder
+                .record(&world)
+                .unwrap_or_else(|e| exit(&format!("Could not write replay: {}", e)));
+        }
+    }
+    if let Some(recorder) = recorder {
+        recorder
+            .finish()
+            .unwrap_or_else(|e| exit(&format!("Could not write replay: {}", e)));
+    }
+
+    println!("Seed: {}", world.get_seed());
+    println!("Ticks: {}", world.get_tick());
+    println!("Ants: {}", world.iter_ants().count());
+    println!("Food delivered: {}", delivered);
+    println!(
+        "Resources remaining: {}",
+        world
+            .iter_resources()
+            .map(|(_, resource)| resource.get_resources_remaining() as u64)
+            .sum::<u64>()
+    );
+}
+
+/// Reads the options from the command line
+fn parse_options() -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {}", flag))?;
+        let number = || {
+            value
+                .parse::<u64>()
+                .map_err(|_| format!("Invalid value for {}: {}", flag, value))
+        };
+        match flag.as_str() {
+            "--ticks" => options.ticks = Some(number()?),
+            "--seed" => options.seed = Some(number()?),
+            "--generate" => options.generate = Some(number()?),
+            "--every" => options.every = Some(number()?),
+            "--tile-size" => match number()? {
+                size @ 1..=MAXIMUM_TILE_SIZE => options.tile_size = Some(size as u32),
+                _ => {
+                    return Err(format!(
+                        "Invalid value for {}: {} (must be from 1 to {})",
+                        flag, value, MAXIMUM_TILE_SIZE
+                    ))
+                }
+            },
+            "--map" => options.map = Some(value),
+            "--record" => options.record = Some(value),
+            "--png-dir" => options.png_dir = Some(value),
+            "--gif" => options.gif = Some(value),
+            "--sweep" => options
+                .sweep
+                .push(SweepAxis::parse(&value).ok_or_else(|| format!("Invalid sweep {}", value))?),
+            "--seeds" => options.seeds = Some(number()?),
+            "--csv" => options.csv = Some(value),
+            "--evolve" => options.evolve = Some(number()? as u32),
+            "--population" => options.population = Some(number()? as usize),
+            "--fitness" => {
+                options.fitness = Some(match value.as_str() {
+                    "tick" => Fitness::DeliveredPerTick,
+                    "ant" => Fitness::DeliveredPerAnt,
+                    _ => return Err(format!("Invalid fitness {}", value)),
+                })
+            }
+            _ => return Err(format!("Unknown option {}", flag)),
+        }
+    }
+    if cfg!(not(feature = "export")) && (options.png_dir.is_some() || options.gif.is_some()) {
+        return Err(String::from(
+            "Exporting frames needs the export feature (cargo run --features export)",
+        ));
+    }
+    Ok(options)
+}
+
+/// Runs the parameter sweep given on the command line, and writes the results as CSV
+fn run_sweep(options: Options) {
+    let sweep = Sweep {
+        axes: options.sweep,
+        first_seed: options.seed.unwrap_or(0),
+        seeds: options.seeds.unwrap_or(DEFAULT_SEEDS).max(1),
+        ticks: options.ticks.unwrap_or(DEFAULT_TICKS),
+    };
+    let results = sweep.run();
+    let written = match &options.csv {
+        Some(path) => {
+            File::create(path).and_then(|file| sweep.write_csv(&results, BufWriter::new(file)))
+        }
+        None => sweep.write_csv(&results, io::stdout()),
+    };
+    if let Err(e) = written {
+        exit(&format!("Could not write results: {}", e));
+    }
+}
+
+/// Runs the evolution given on the command line, printing the best parameters of each generation
+fn run_evolution(options: Options) {
+    let defaults = EvolutionSettings::default();
+    let settings = EvolutionSettings {
+        population_size: options.population.unwrap_or(defaults.population_size),
+        generations: options.evolve.unwrap_or(defaults.generations),
+        seeds: options.seeds.unwrap_or(defaults.seeds).max(1),
+        ticks: options.ticks.unwrap_or(defaults.ticks),
+        fitness: options.fitness.unwrap_or(defaults.fitness),
+        seed: options.seed.unwrap_or(defaults.seed),
+        ..defaults
+    };
+    evolution::evolve(&settings, |generation| {
+        println!(
+            "Generation {}: best {:.4}, mean {:.4}",
+            generation.index, generation.best_fitness, generation.mean_fitness
+        );
+        for ant_type in ANT_TYPES.iter() {
+            let parameters = &generation.best.ant_types[*ant_type];
+            println!(
+                "  {}: pheromone chance {:.3}, backwards chance {:.3}, max steps {}, deposit amount {}",
+                ant_type,
+                parameters.pheromone_chance,
+                parameters.backwards_chance,
+                parameters.max_steps,
+                parameters.deposit_amount
+            );
+        }
+    });
+}
+
+/// Prints the message and stops with an error
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    process::exit(1)
+}
+
+/// Writes frames to the image files given on the command line
+#[cfg(feature = "export")]
+struct Exporter {
+    tile_size: u32,
+    png_dir: Option<std::path::PathBuf>,
+    gif: Option<sim::export::GifWriter<std::io::BufWriter<File>>>,
+}
+
+#[cfg(feature = "export")]
+impl Exporter {
+    fn new(options: &Options) -> Exporter {
+        let tile_size = options.tile_size.unwrap_or(DEFAULT_TILE_SIZE);
+        let png_dir = options.png_dir.as_ref().map(std::path::PathBuf::from);
+        if let Some(dir) = &png_dir {
+            std::fs::create_dir_all(dir)
+                .unwrap_or_else(|e| exit(&format!("Could not create {}: {}", dir.display(), e)));
+        }
+        let gif = options.gif.as_ref().map(|path| {
+            File::create(path)
+                .and_then(|file| {
+                    sim::export::GifWriter::new(
+                        std::io::BufWriter::new(file),
+                        tile_size,
+                        GIF_FRAME_DELAY,
+                    )
+                })
+                .unwrap_or_else(|e| exit(&format!("Could not create {}: {}", path, e)))
+        });
+        Exporter {
+            tile_size,
+            png_dir,
+            gif,
+        }
+    }
+
+    fn export(&mut self, world: &World) {
+        if self.png_dir.is_none() && self.gif.is_none() {
+            return;
+        }
+        let frame = sim::raster::Frame::new(world, self.tile_size);
+        if let Some(dir) = &self.png_dir {
+            let path = dir.join(format!("frame_{:06}.png", world.get_tick()));
+            File::create(&path)
+                .and_then(|file| sim::export::write_png(&frame, std::io::BufWriter::new(file)))
+                .unwrap_or_else(|e| exit(&format!("Could not write {}: {}", path.display(), e)));
+        }
+        if let Some(gif) = &mut self.gif {
+            gif.add_frame(&frame)
+                .unwrap_or_else(|e| exit(&format!("Could not write GIF: {}", e)));
+        }
+    }
+}
+
+/// Does nothing, as frames can't be exported without the `export` feature
+#[cfg(not(feature = "export"))]
+struct Exporter;
+
+#[cfg(not(feature = "export"))]
+impl Exporter {
+    fn new(_options: &Options) -> Exporter {
+        Exporter
+    }
+
+    fn export(&mut self, _world: &World) {}
 }
@@ -1,12 +1,23 @@
-use crate::ant::{Ant, AntType};
-use crate::ant_settings::{
-    DEBUG_MODE, DEFAULT_COLONY_SPAWN_RATE, WORLD_HEIGHT, WORLD_WIDTH,
-};
-use crate::pheromone::{Pheromone, PheromoneType};
-use crate::resource::Resource;
+use crate::ant::{Ant, AntType, AntUpdate, ANT_TYPES};
+use crate::ant_settings::{DEBUG_MODE, DEFAULT_COLONY_SPAWN_RATE, WORLD_HEIGHT, WORLD_WIDTH};
+use crate::ant_store::{AntId, AntStore};
+use crate::behaviour::{Behaviour, DefaultBehaviour};
+use crate::mix_seed;
+use crate::pheromone_field::PheromoneField;
+use crate::resource::{ResourceKind, RESOURCE_KINDS};
+use crate::world::{AntCountMap, ResourceMap, TerrainMap};
 use crate::Coordinates;
 use enum_map::EnumMap;
-use std::collections::HashMap;
+use rand::SeedableRng;
+use rand_pcg::Pcg64Mcg;
+
+/// The changes made by a colony during a time step
+pub(crate) struct ColonyUpdate {
+    /// The identifier and type of every ant that was spawned
+    pub(crate) spawned: Vec<(AntId, AntType)>,
+    /// The changes to the world requested by every ant, with its identifier
+    pub(crate) ants: Vec<(AntId, AntUpdate)>,
+}
 
 /// A container for a group of ants
 ///
@@ -14,46 +25,145 @@ use std::collections::HashMap;
 pub struct Colony {
     /// The coordinates of the colony position
     pub(crate) position: Coordinates,
-    /// Stores all ants, by their type
-    // TODO Switch to a faster map
-    pub(crate) ants: HashMap<AntType, Vec<Ant>>,
+    /// Stores all ants
+    pub(crate) ants: AntStore,
     /// The maximum number of ants that can be spawned per time step
     spawn_rate: u16,
+    /// The number of ants of each type the colony aims to have
+    target_population: EnumMap<AntType, u16>,
+    /// The decision logic used by every ant in this colony
+    behaviour: Box<dyn Behaviour>,
+    /// The nutrition stored from the food delivered, by kind
+    stock: EnumMap<ResourceKind, u32>,
+    /// The nutrition of each kind taken from the stock to spawn a single ant
+    spawn_cost: EnumMap<ResourceKind, u32>,
 }
 
 impl Default for Colony {
     fn default() -> Self {
-        let mut ants = HashMap::new();
-        ants.insert(AntType::Scout, Vec::new());
-        ants.insert(AntType::Worker, Vec::new());
-        Colony {
-            position: Coordinates::new(WORLD_WIDTH / 2, WORLD_HEIGHT / 2).unwrap(),
-            ants,
-            spawn_rate: DEFAULT_COLONY_SPAWN_RATE,
-        }
+        Colony::new(Coordinates::new(WORLD_WIDTH / 2, WORLD_HEIGHT / 2).unwrap())
     }
 }
 impl Colony {
     /// Builds a new colony at the given position
-    ///
-    /// Ant types have to be added manually
     pub fn new(position: Coordinates) -> Colony {
         Colony {
             position,
-            ants: HashMap::new(),
+            ants: AntStore::new(position),
             spawn_rate: DEFAULT_COLONY_SPAWN_RATE,
+            target_population: EnumMap::from(|ant_type: AntType| {
+                ant_type.get_maximum_number_of_ants()
+            }),
+            behaviour: Box::new(DefaultBehaviour::default()),
+            stock: EnumMap::default(),
+            spawn_cost: EnumMap::default(),
+        }
+    }
+
+    /// Sets the maximum number of ants that can be spawned per time step
+    pub fn set_spawn_rate(&mut self, spawn_rate: u16) {
+        self.spawn_rate = spawn_rate;
+    }
+
+    /// Sets the number of ants of the given type the colony aims to have
+    ///
+    /// Defaults to the colony sizes defined in [`ant_settings`]
+    /// # Example
+    /// ```
+    /// # use sim::ant::AntType;
+    /// # use sim::colony::Colony;
+    /// let mut colony = Colony::default();
+    /// colony.set_target_population(AntType::Worker, 500);
+    /// assert_eq!(colony.get_target_population(AntType::Worker), 500);
+    /// ```
+    pub fn set_target_population(&mut self, ant_type: AntType, amount: u16) {
+        self.target_population[ant_type] = amount;
+    }
+
+    /// The number of ants of the given type the colony aims to have
+    pub fn get_target_population(&self, ant_type: AntType) -> u16 {
+        self.target_population[ant_type]
+    }
+
+    /// Replaces the decision logic used by every ant in this colony
+    ///
+    /// # Example
+    /// ```
+    /// # use sim::behaviour::DefaultBehaviour;
+    /// # use sim::colony::Colony;
+    /// let mut colony = Colony::default();
+    /// colony.set_behaviour(Box::new(DefaultBehaviour::default()));
+    /// ```
+    pub fn set_behaviour(&mut self, behaviour: Box<dyn Behaviour>) {
+        self.behaviour = behaviour;
+    }
+    /// Sets the nutrition of the given kind taken from the stock to spawn a single ant
+    ///
+    /// Spawning is free by default. Once costs are set, ants are only spawned while the stock holds enough of every
+    /// kind, so a colony may need a mix of food to grow
+    /// # Example
+    /// ```
+    /// # use sim::colony::Colony;
+    /// # use sim::resource::ResourceKind;
+    /// # use sim::world::World;
+    /// let mut colony = Colony::default();
+    /// colony.set_spawn_cost(ResourceKind::Protein, 3);
+    /// colony.add_stock(ResourceKind::Protein, 6);
+    ///
+    /// let mut world = World::new_with_data(Vec::new(), vec![colony]);
+    /// for _ in 0..5 {
+    ///     world.update();
+    /// }
+    /// // Only enough protein was stored for two ants
+    /// let colony = &world.get_colonies()[0];
+    /// assert_eq!(colony.iter_ants().count(), 2);
+    /// assert_eq!(colony.get_stock(ResourceKind::Protein), 0);
+    /// ```
+    pub fn set_spawn_cost(&mut self, kind: ResourceKind, amount: u32) {
+        self.spawn_cost[kind] = amount;
+    }
+
+    /// The nutrition of the given kind taken from the stock to spawn a single ant
+    pub fn get_spawn_cost(&self, kind: ResourceKind) -> u32 {
+        self.spawn_cost[kind]
+    }
+
+    /// Adds nutrition of the given kind to the stock
+    pub fn add_stock(&mut self, kind: ResourceKind, amount: u32) {
+        self.stock[kind] = self.stock[kind].saturating_add(amount);
+    }
+
+    /// The nutrition of the given kind stored from the food delivered
+    pub fn get_stock(&self, kind: ResourceKind) -> u32 {
+        self.stock[kind]
+    }
+
+    /// Takes the cost of a single ant from the stock, returning false if there is not enough of every kind
+    fn pay_spawn_cost(&mut self) -> bool {
+        if RESOURCE_KINDS
+            .iter()
+            .any(|kind| self.stock[*kind] < self.spawn_cost[*kind])
+        {
+            return false;
         }
+        for kind in &RESOURCE_KINDS {
+            self.stock[*kind] -= self.spawn_cost[*kind];
+        }
+        true
     }
+
     /// Spawns the maximum amount of ants that are allowed each turn
     ///
-    fn spawn_ants(&mut self) {
-        let mut total_required_ants = 0;
+    /// Returns the identifier and type of every ant that was spawned
+    fn spawn_ants(&mut self) -> Vec<(AntId, AntType)> {
+        let mut spawned = Vec::new();
+        let mut total_required_ants: u64 = 0;
         let mut ants_spawn = Vec::new();
 
         // Counts the number of ants that are required, for each type
-        for (ant_type, ants) in &self.ants {
-            let max_ants = ant_type.get_maximum_number_of_ants() as u16;
-            let required = max_ants - ants.len() as u16;
+        for ant_type in &ANT_TYPES {
+            let max_ants = self.target_population[*ant_type] as u64;
+            let required = max_ants.saturating_sub(self.ants.count(*ant_type) as u64);
             if DEBUG_MODE {
                 println!(
                     "Type: {} has maximum of {} and required: {}",
@@ -62,7 +172,7 @@ impl Colony {
             }
             if required > 0 {
                 ants_spawn.push((*ant_type, required));
-                total_required_ants += required as u16;
+                total_required_ants += required;
             }
         }
         if DEBUG_MODE {
@@ -71,41 +181,63 @@ impl Colony {
         }
         // Allocates and spawns the number of ants that can be spawned this turn, between the number of ants that are required per type
         for (ant_type, amount) in ants_spawn {
-            let mut to_spawn = amount * (self.spawn_rate * 100) / total_required_ants;
-            to_spawn /= 100;
+            let mut to_spawn = amount * (self.spawn_rate as u64 * 100) / total_required_ants;
+            // A spawn rate above the number required would otherwise overshoot the target population
+            to_spawn = (to_spawn / 100).min(amount);
             if DEBUG_MODE {
                 println!(
                     "Spawning: {} for type: {} at Position {} with required: {}",
                     to_spawn, ant_type, self.position, amount
                 );
             }
-            let ant_container = self
-                .ants
-                .get_mut(&ant_type)
-                .unwrap_or_else(|| panic!("Failed to get ant type {}", ant_type));
             for _ in 0..to_spawn {
-                ant_container.push(Ant::new(ant_type, self.position, self.position));
+                if !self.pay_spawn_cost() {
+                    return spawned;
+                }
+                spawned.push((self.ants.push(ant_type, self.position), ant_type));
             }
         }
+        spawned
     }
 
     /// Spawns the maximum amount of ants it can for this time step
     ///
-    /// And updates the position of all the ants in this colony
-    pub fn update(
+    /// And decides the movement of all the ants in this colony, returning the changes they make to the world
+    ///
+    /// Every ant draws from its own random number generator, derived from the given seed
+    pub(crate) fn update(
         &mut self,
-        food_map: &mut [[Option<Resource>; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize],
-        pheromones_lookup: &mut Vec<(Coordinates, PheromoneType)>,
-        pheromones_map: &mut [[EnumMap<PheromoneType, Option<Pheromone>>; WORLD_HEIGHT as usize];
-                 WORLD_WIDTH as usize],
-    ) {
-        self.spawn_ants();
-
-        for (_, ants) in self.ants.iter_mut() {
-            for ant in ants {
-                ant.update(food_map, pheromones_lookup, pheromones_map);
-            }
-        }
+        food_map: &ResourceMap,
+        pheromones_map: &PheromoneField,
+        ant_counts: &AntCountMap,
+        terrain: &TerrainMap,
+        seed: u64,
+    ) -> ColonyUpdate {
+        let spawned = self.spawn_ants();
+
+        let behaviour = self.behaviour.as_ref();
+        let ants = self.ants.update(|id, ant| {
+            let mut rng = Pcg64Mcg::seed_from_u64(mix_seed(seed, &[id.get_id()]));
+            ant.update(
+                behaviour,
+                food_map,
+                pheromones_map,
+                ant_counts,
+                terrain,
+                &mut rng,
+            )
+        });
+        ColonyUpdate { spawned, ants }
+    }
+
+    /// Loads the ant with the given identifier, if it exists, with food of the given kind
+    pub(crate) fn pick_up(&mut self, id: AntId, kind: ResourceKind) {
+        self.ants.pick_up(id, kind)
+    }
+
+    /// Removes the ant with the given identifier, returning it if it existed
+    pub(crate) fn remove_ant(&mut self, id: AntId) -> Option<Ant> {
+        self.ants.remove(id)
     }
 
     /// Returns a copy of the colony position
@@ -113,8 +245,13 @@ impl Colony {
         self.position
     }
 
-    /// Returns a iterator for all ants in the colony, contained by AntType
-    pub fn iter_ants(&self) -> std::collections::hash_map::Iter<AntType, Vec<Ant>> {
+    /// Returns an iterator over a copy of every ant in the colony, with its identifier
+    pub fn iter_ants(&self) -> impl Iterator<Item = (AntId, Ant)> + '_ {
         self.ants.iter()
     }
+
+    /// Returns the store containing every ant in the colony
+    pub fn get_ants(&self) -> &AntStore {
+        &self.ants
+    }
 }
@@ -0,0 +1,189 @@
+//! Generators for where resources are placed in a world
+use crate::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use crate::resource::Resource;
+use crate::world::World;
+use crate::Coordinates;
+use rand::prelude::SliceRandom;
+use rand::Rng;
+
+/// A ring around the colonies, and how many resources to place in it
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DistanceBand {
+    /// The smallest straight line distance from the nearest colony, inclusive
+    pub minimum: f64,
+    /// The largest straight line distance from the nearest colony, inclusive
+    pub maximum: f64,
+    /// The number of resources to place in the band
+    pub count: u8,
+    /// The amount each resource in the band starts with, so bands can get richer (or poorer) further out
+    pub amount: u32,
+}
+
+/// The possible ways of choosing where resources are placed
+///
+/// * Uniform - `count` resources on random tiles
+/// * Clustered - `clusters` patches of up to `per_cluster` resources, each within `radius` tiles (along either axis)
+///   of a random centre
+/// * PoissonDisc - Up to `count` random resources, where no two are closer than `minimum_distance`
+/// * DistanceBands - Resources at random tiles within each band of distance from the nearest colony
+/// * Explicit - Exactly the given resources, at the given positions
+///
+/// Resources are only ever placed on open tiles without a resource or a colony, so a generator places fewer than
+/// asked for (rather than looping forever) once there is no room left
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResourceDistribution {
+    Uniform {
+        count: u8,
+    },
+    Clustered {
+        clusters: u8,
+        per_cluster: u8,
+        radius: u16,
+    },
+    PoissonDisc {
+        count: u8,
+        minimum_distance: f64,
+    },
+    DistanceBands(Vec<DistanceBand>),
+    Explicit(Vec<(Coordinates, Resource)>),
+}
+
+impl ResourceDistribution {
+    /// Chooses where resources should be placed in the given world, without changing it
+    ///
+    /// Every generated resource is a copy of `resource`, except in distance bands, which set their own amount, and
+    /// explicit lists, which give their own resources
+    /// # Example
+    /// ```
+    /// # use rand::SeedableRng;
+    /// # use rand::rngs::StdRng;
+    /// # use sim::distribution::ResourceDistribution;
+    /// # use sim::resource::Resource;
+    /// # use sim::world::World;
+    /// let world = World::new_with_data(Vec::new(), Vec::new());
+    /// let distribution = ResourceDistribution::PoissonDisc { count: 10, minimum_distance: 3.0 };
+    /// let placed = distribution.generate(&world, Resource::default(), &mut StdRng::seed_from_u64(1));
+    ///
+    /// for (index, (first, _)) in placed.iter().enumerate() {
+    ///     for (second, _) in &placed[index + 1..] {
+    ///         assert!(first.euclidean_distance(*second) >= 3.0);
+    ///     }
+    /// }
+    /// ```
+    pub fn generate<R: Rng + ?Sized>(
+        &self,
+        world: &World,
+        resource: Resource,
+        rng: &mut R,
+    ) -> Vec<(Coordinates, Resource)> {
+        let mut free = free_tiles(world);
+        match self {
+            ResourceDistribution::Uniform { count } => {
+                free.shuffle(rng);
+                free.truncate(*count as usize);
+                free.into_iter().map(|coords| (coords, resource)).collect()
+            }
+            ResourceDistribution::Clustered {
+                clusters,
+                per_cluster,
+                radius,
+            } => {
+                let mut placed = Vec::new();
+                for _ in 0..*clusters {
+                    let centre = match free.choose(rng) {
+                        Some(centre) => *centre,
+                        None => break,
+                    };
+                    let mut patch: Vec<Coordinates> = free
+                        .iter()
+                        .copied()
+                        .filter(|coords| coords.chebyshev_distance(centre) <= *radius)
+                        .collect();
+                    patch.shuffle(rng);
+                    patch.truncate(*per_cluster as usize);
+                    free.retain(|coords| !patch.contains(coords));
+                    placed.extend(patch.into_iter().map(|coords| (coords, resource)));
+                }
+                placed
+            }
+            ResourceDistribution::PoissonDisc {
+                count,
+                minimum_distance,
+            } => {
+                // Random sequential placement over the grid: each tile is tried once, in a random order
+                free.shuffle(rng);
+                let mut placed: Vec<Coordinates> = Vec::new();
+                for coords in free {
+                    if placed.len() >= *count as usize {
+                        break;
+                    }
+                    if placed
+                        .iter()
+                        .all(|other| coords.euclidean_distance(*other) >= *minimum_distance)
+                    {
+                        placed.push(coords);
+                    }
+                }
+                placed
+                    .into_iter()
+                    .map(|coords| (coords, resource))
+                    .collect()
+            }
+            ResourceDistribution::DistanceBands(bands) => {
+                let colonies: Vec<Coordinates> = world
+                    .get_colonies()
+                    .iter()
+                    .map(|colony| colony.get_position())
+                    .collect();
+                let mut placed = Vec::new();
+                for band in bands {
+                    let mut ring: Vec<Coordinates> = free
+                        .iter()
+                        .copied()
+                        .filter(|coords| {
+                            let distance = colonies
+                                .iter()
+                                .map(|colony| coords.euclidean_distance(*colony))
+                                .fold(f64::INFINITY, f64::min);
+                            distance >= band.minimum && distance <= band.maximum
+                        })
+                        .collect();
+                    ring.shuffle(rng);
+                    ring.truncate(band.count as usize);
+                    free.retain(|coords| !ring.contains(coords));
+                    let banded = Resource::new_of_kind(resource.get_kind(), band.amount);
+                    placed.extend(ring.into_iter().map(|coords| (coords, banded)));
+                }
+                placed
+            }
+            ResourceDistribution::Explicit(resources) => {
+                let mut placed: Vec<(Coordinates, Resource)> = Vec::new();
+                for (coords, resource) in resources {
+                    if free.contains(coords) {
+                        free.retain(|other| other != coords);
+                        placed.push((*coords, *resource));
+                    }
+                }
+                placed
+            }
+        }
+    }
+}
+
+/// Returns every open tile without a resource or a colony, in a fixed order
+fn free_tiles(world: &World) -> Vec<Coordinates> {
+    let occupied: Vec<Coordinates> = world
+        .iter_resources()
+        .map(|(coords, _)| coords)
+        .chain(
+            world
+                .get_colonies()
+                .iter()
+                .map(|colony| colony.get_position()),
+        )
+        .collect();
+    (0..WORLD_WIDTH)
+        .flat_map(|x| (0..WORLD_HEIGHT).map(move |y| Coordinates::new(x, y).unwrap()))
+        .filter(|coords| world.get_terrain(*coords).is_passable() && !occupied.contains(coords))
+        .collect()
+}
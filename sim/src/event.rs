@@ -0,0 +1,77 @@
+use crate::ant::AntType;
+use crate::ant_store::AntId;
+use crate::pheromone::PheromoneType;
+use crate::resource::ResourceKind;
+use crate::Coordinates;
+
+/// Something that happened in the world during a time step
+///
+/// Ants are identified by the index of their colony, and their identifier within it
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Event {
+    /// A new ant was spawned at its colony
+    AntSpawned {
+        colony: usize,
+        id: AntId,
+        ant_type: AntType,
+    },
+    /// An ant was removed from the world, by [`World::remove_ant`](crate::world::World::remove_ant) or
+    /// [`World::remove_colony`](crate::world::World::remove_colony)
+    ///
+    /// Ants never die during [`World::update`](crate::world::World::update), so it doesn't emit this event
+    AntDied {
+        colony: usize,
+        id: AntId,
+        position: Coordinates,
+    },
+    /// An ant took one unit of food from a resource
+    FoodPickedUp {
+        colony: usize,
+        id: AntId,
+        position: Coordinates,
+        kind: ResourceKind,
+    },
+    /// An ant carrying food reached its colony, and added it to the colony's stock
+    FoodDelivered {
+        colony: usize,
+        id: AntId,
+        position: Coordinates,
+        kind: ResourceKind,
+    },
+    /// The last unit of food was taken from a resource, and it was removed
+    ResourceDepleted { position: Coordinates },
+    /// A resource was placed by the resource policy, by regrowing or respawning
+    ResourceSpawned { position: Coordinates },
+    /// A pheromone was laid on a tile that did not already have one of the same type
+    PheromoneCreated {
+        position: Coordinates,
+        pheromone_type: PheromoneType,
+    },
+    /// A pheromone decayed to nothing, and was removed
+    PheromoneExpired {
+        position: Coordinates,
+        pheromone_type: PheromoneType,
+    },
+}
+
+/// Receives every event emitted by a [`World`](crate::world::World), along with the tick it happened on
+///
+/// Implemented for closures, so an observer can be as simple as
+/// ```
+/// # use sim::event::Event;
+/// # use sim::world::World;
+/// let mut world = World::new_with_seed(1);
+/// world.add_observer(Box::new(|tick: u64, event: &Event| println!("{}: {:?}", tick, event)));
+/// ```
+pub trait Observer: Send {
+    fn notify(&mut self, tick: u64, event: &Event);
+}
+
+impl<F> Observer for F
+where
+    F: FnMut(u64, &Event) + Send,
+{
+    fn notify(&mut self, tick: u64, event: &Event) {
+        self(tick, event)
+    }
+}
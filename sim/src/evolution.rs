@@ -0,0 +1,220 @@
+//! A genetic algorithm, for evolving the parameters of the default behaviour rather than picking them by hand
+use crate::ant::ANT_TYPES;
+use crate::ant_settings::{
+    EVOLUTION_ELITES, EVOLUTION_GENERATIONS, EVOLUTION_MUTATION_CHANCE,
+    EVOLUTION_MUTATION_STRENGTH, EVOLUTION_POPULATION_SIZE, EVOLUTION_SEEDS, EVOLUTION_TICKS,
+    EVOLUTION_TOURNAMENT_SIZE,
+};
+use crate::behaviour::AntParameters;
+use crate::mix_seed;
+use crate::sweep::{get_seeds, map_in_parallel, run_once};
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64Mcg;
+use std::cmp::Ordering;
+
+/// Mixed into the seed, so the algorithm draws different numbers to the worlds it evaluates in
+const EVOLUTION_KEY: u64 = 0x6576_6f6c_7665;
+
+/// The genes evolved for every ant type, as the parameter name after the ant type, with the range of values allowed
+const GENES: [(&str, f64, f64); 4] = [
+    ("PHEROMONE_CHANCE", 0.0, 1.0),
+    ("BACKWARDS_CHANCE", 0.0, 1.0),
+    ("MAX_STEPS", 10.0, 2000.0),
+    ("DEPOSIT_AMOUNT", 1.0, 100.0),
+];
+
+/// The possible measures of how well a set of parameters forages, averaged over every evaluation
+///
+/// * DeliveredPerTick - The food delivered to the colony per tick
+/// * DeliveredPerAnt - The food delivered per ant, per tick, which rewards smaller colonies doing the same work
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Fitness {
+    #[default]
+    DeliveredPerTick,
+    DeliveredPerAnt,
+}
+
+/// The settings of the genetic algorithm, defaulting to the values in [`ant_settings`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct EvolutionSettings {
+    /// The number of candidates in each generation
+    pub population_size: usize,
+    /// The number of generations, including the first
+    pub generations: u32,
+    /// The number of seeded worlds each candidate is evaluated in
+    pub seeds: u64,
+    /// The number of ticks each evaluation runs for
+    pub ticks: u64,
+    /// The number of best candidates carried over unchanged to the next generation
+    pub elites: usize,
+    /// The number of candidates compared when choosing each parent
+    pub tournament_size: usize,
+    /// The probability of each gene mutating in a child
+    pub mutation_chance: f64,
+    /// The largest change made by a mutation, as a share of the gene's range
+    pub mutation_strength: f64,
+    pub fitness: Fitness,
+    /// The seed of the first world candidates are evaluated in, which also seeds the algorithm itself
+    pub seed: u64,
+}
+
+impl Default for EvolutionSettings {
+    fn default() -> Self {
+        EvolutionSettings {
+            population_size: EVOLUTION_POPULATION_SIZE,
+            generations: EVOLUTION_GENERATIONS,
+            seeds: EVOLUTION_SEEDS,
+            ticks: EVOLUTION_TICKS,
+            elites: EVOLUTION_ELITES,
+            tournament_size: EVOLUTION_TOURNAMENT_SIZE,
+            mutation_chance: EVOLUTION_MUTATION_CHANCE,
+            mutation_strength: EVOLUTION_MUTATION_STRENGTH,
+            fitness: Fitness::default(),
+            seed: 0,
+        }
+    }
+}
+
+/// A summary of a single generation
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Generation {
+    /// The number of generations before this one
+    pub index: u32,
+    /// The fittest parameters in the generation
+    pub best: AntParameters,
+    pub best_fitness: f64,
+    pub mean_fitness: f64,
+}
+
+/// Evolves the pheromone chance, backwards chance, maximum steps and deposit amount of every ant type, reporting
+/// each generation as it is evaluated, and returns the last generation
+///
+/// The first generation holds the parameters from [`ant_settings`] along with random ones. Each generation after
+/// keeps the fittest candidates, and fills the rest with children of parents chosen by tournament, mixing their
+/// genes and mutating some. Every candidate is evaluated in parallel, in the same seeded worlds, so the same settings
+/// always evolve the same parameters, and while any elites are kept the best fitness never falls
+/// # Example
+/// ```
+/// # use sim::evolution::{evolve, EvolutionSettings};
+/// let settings = EvolutionSettings {
+///     population_size: 4,
+///     generations: 3,
+///     seeds: 1,
+///     ticks: 30,
+///     ..EvolutionSettings::default()
+/// };
+/// let mut reports = Vec::new();
+/// let last = evolve(&settings, |generation| reports.push(*generation));
+///
+/// assert_eq!(reports.len(), 3);
+/// assert_eq!(reports[2], last);
+/// assert!(reports[0].best_fitness <= last.best_fitness);
+/// ```
+pub fn evolve<F: FnMut(&Generation)>(settings: &EvolutionSettings, mut report: F) -> Generation {
+    let genes = get_genes();
+    let size = settings.population_size.max(1);
+    let mut rng = Pcg64Mcg::seed_from_u64(mix_seed(settings.seed, &[EVOLUTION_KEY]));
+
+    let mut candidates = vec![AntParameters::default()];
+    while candidates.len() < size {
+        let mut candidate = AntParameters::default();
+        for (name, minimum, maximum) in &genes {
+            candidate.set(name, rng.gen_range(*minimum, *maximum));
+        }
+        candidates.push(candidate);
+    }
+    let mut scored = evaluate(candidates, settings);
+    let mut index = 0;
+    loop {
+        scored.sort_by(|first, second| second.1.partial_cmp(&first.1).unwrap_or(Ordering::Equal));
+        let generation = Generation {
+            index,
+            best: scored[0].0,
+            best_fitness: scored[0].1,
+            mean_fitness: scored.iter().map(|(_, fitness)| fitness).sum::<f64>()
+                / scored.len() as f64,
+        };
+        report(&generation);
+        index += 1;
+        if index >= settings.generations.max(1) {
+            return generation;
+        }
+
+        let elites = settings.elites.min(size);
+        let mut children = Vec::with_capacity(size - elites);
+        while children.len() < size - elites {
+            let first = choose_parent(&scored, settings.tournament_size, &mut rng);
+            let second = choose_parent(&scored, settings.tournament_size, &mut rng);
+            let mut child = first;
+            for (name, minimum, maximum) in &genes {
+                let mut value = if rng.gen::<bool>() {
+                    first.get(name)
+                } else {
+                    second.get(name)
+                }
+                .unwrap_or(*minimum);
+                if rng.gen::<f64>() < settings.mutation_chance {
+                    let change = settings.mutation_strength * (maximum - minimum);
+                    value += rng.gen_range(-1.0, 1.0) * change;
+                }
+                child.set(name, value.clamp(*minimum, *maximum));
+            }
+            children.push(child);
+        }
+        scored.truncate(elites);
+        scored.extend(evaluate(children, settings));
+    }
+}
+
+/// Returns the full name of every evolved gene, with the range of values allowed
+fn get_genes() -> Vec<(String, f64, f64)> {
+    ANT_TYPES
+        .iter()
+        .flat_map(|ant_type| {
+            GENES.iter().map(move |(field, minimum, maximum)| {
+                let name = format!("{}_{}", ant_type.to_string().to_uppercase(), field);
+                (name, *minimum, *maximum)
+            })
+        })
+        .collect()
+}
+
+/// Picks the fittest of a few random candidates
+fn choose_parent<R: Rng>(
+    scored: &[(AntParameters, f64)],
+    tournament_size: usize,
+    rng: &mut R,
+) -> AntParameters {
+    (0..tournament_size.max(1))
+        .map(|_| scored[rng.gen_range(0, scored.len())])
+        .fold(
+            None,
+            |best: Option<(AntParameters, f64)>, entrant| match best {
+                Some(best) if best.1 >= entrant.1 => Some(best),
+                _ => Some(entrant),
+            },
+        )
+        .map(|(parameters, _)| parameters)
+        .unwrap_or_default()
+}
+
+/// Runs every candidate in every seeded world, returning each with its fitness
+fn evaluate(
+    candidates: Vec<AntParameters>,
+    settings: &EvolutionSettings,
+) -> Vec<(AntParameters, f64)> {
+    let fitness = |parameters: &AntParameters| {
+        let total: f64 = get_seeds(settings.seed, settings.seeds.max(1))
+            .map(|seed| {
+                let (delivered, efficiency) = run_once(*parameters, seed, settings.ticks);
+                match settings.fitness {
+                    Fitness::DeliveredPerTick => delivered as f64 / settings.ticks.max(1) as f64,
+                    Fitness::DeliveredPerAnt => efficiency,
+                }
+            })
+            .sum();
+        total / settings.seeds.max(1) as f64
+    };
+    let scores = map_in_parallel(&candidates, fitness);
+    candidates.into_iter().zip(scores).collect()
+}
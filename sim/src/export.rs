@@ -0,0 +1,79 @@
+//! Writing frames from the software renderer as image files
+//!
+//! Only available with the `export` feature
+use crate::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use crate::raster::Frame;
+use gif::SetParameter;
+use std::io;
+use std::io::Write;
+
+/// How hard the GIF encoder works to choose a palette for each frame, from 1 (best) to 30 (fastest)
+const GIF_QUANTIZE_SPEED: i32 = 10;
+
+/// Writes a single frame as a PNG image
+/// # Example
+/// ```
+/// # use sim::export::write_png;
+/// # use sim::raster::Frame;
+/// # use sim::world::World;
+/// let frame = Frame::new(&World::new_with_seed(2), 2);
+/// let mut file = Vec::new();
+/// write_png(&frame, &mut file).unwrap();
+/// assert_eq!(&file[1..4], b"PNG");
+/// ```
+pub fn write_png<W: Write>(frame: &Frame, writer: W) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(writer, frame.get_width(), frame.get_height());
+    encoder.set_color(png::ColorType::RGBA);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(frame.get_pixels())?;
+    Ok(())
+}
+
+/// Writes frames one at a time to an animated GIF, that loops forever
+/// # Example
+/// ```
+/// # use sim::export::GifWriter;
+/// # use sim::raster::Frame;
+/// # use sim::world::World;
+/// let mut world = World::new_with_seed(2);
+/// let mut file = Vec::new();
+/// let mut gif = GifWriter::new(&mut file, 2, 10).unwrap();
+/// for _ in 0..3 {
+///     world.update();
+///     gif.add_frame(&Frame::new(&world, 2)).unwrap();
+/// }
+/// drop(gif);
+/// assert_eq!(&file[0..3], b"GIF");
+/// ```
+pub struct GifWriter<W: Write> {
+    encoder: gif::Encoder<W>,
+    /// The time each frame is shown for, in hundredths of a second
+    delay: u16,
+}
+
+impl<W: Write> GifWriter<W> {
+    /// Starts a GIF sized for frames drawn with the given tile size, showing each frame for `delay` hundredths of a
+    /// second
+    pub fn new(writer: W, tile_size: u32, delay: u16) -> io::Result<GifWriter<W>> {
+        let size = |tiles: u16| (tiles as u32 * tile_size.max(1)).min(u16::MAX as u32) as u16;
+        let mut encoder = gif::Encoder::new(writer, size(WORLD_WIDTH), size(WORLD_HEIGHT), &[])?;
+        encoder.set(gif::Repeat::Infinite)?;
+        Ok(GifWriter { encoder, delay })
+    }
+
+    /// Adds a frame to the end of the animation
+    ///
+    /// The frame must have been drawn with the tile size the writer was created with
+    pub fn add_frame(&mut self, frame: &Frame) -> io::Result<()> {
+        let mut pixels = frame.get_pixels().to_vec();
+        let mut gif_frame = gif::Frame::from_rgba_speed(
+            frame.get_width() as u16,
+            frame.get_height() as u16,
+            &mut pixels,
+            GIF_QUANTIZE_SPEED,
+        );
+        gif_frame.delay = self.delay;
+        self.encoder.write_frame(&gif_frame)
+    }
+}
@@ -0,0 +1,317 @@
+//! Procedural generation of whole worlds, with terrain shaped by seeded noise
+use crate::ant_settings::{
+    DEFAULT_RESOURCE_COUNT, GENERATOR_ATTEMPTS, GENERATOR_MINIMUM_REACHABLE_FRACTION,
+    GENERATOR_NOISE_SCALE, GENERATOR_SLOW_THRESHOLD, GENERATOR_WALL_THRESHOLD,
+    GENERATOR_WET_THRESHOLD, WORLD_HEIGHT, WORLD_WIDTH,
+};
+use crate::behaviour::MOVE_POSSIBILITIES;
+use crate::colony::Colony;
+use crate::resource::{Resource, ResourceKind};
+use crate::terrain::Terrain;
+use crate::world::World;
+use crate::{mix_seed, Coordinates};
+use rand::prelude::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64Mcg;
+use std::collections::VecDeque;
+
+/// Mixed into the world seed, so generation draws different numbers to the simulation itself
+const GENERATION_KEY: u64 = 0x6765_6e65_7261_7465;
+
+/// The settings used to generate a world, defaulting to the values in [`ant_settings`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GeneratorSettings {
+    /// How quickly the terrain changes between tiles, with smaller values giving larger features
+    pub noise_scale: f64,
+    /// The noise value above which a tile is a wall
+    pub wall_threshold: f64,
+    /// The noise value above which a tile is slow ground
+    pub slow_threshold: f64,
+    /// The moisture value above which a resource is water
+    pub wet_threshold: f64,
+    /// The number of colonies to place
+    pub colonies: u8,
+    /// The number of resources to place
+    pub resources: u8,
+}
+
+impl Default for GeneratorSettings {
+    fn default() -> Self {
+        GeneratorSettings {
+            noise_scale: GENERATOR_NOISE_SCALE,
+            wall_threshold: GENERATOR_WALL_THRESHOLD,
+            slow_threshold: GENERATOR_SLOW_THRESHOLD,
+            wet_threshold: GENERATOR_WET_THRESHOLD,
+            colonies: 1,
+            resources: DEFAULT_RESOURCE_COUNT,
+        }
+    }
+}
+
+/// Generates a new world, with noise shaped terrain, and colonies and resources placed to suit it
+///
+/// * Terrain - Walls on the highest ground, slow ground on the slopes beneath them, and open ground elsewhere.
+///   Pockets that can't be reached from the largest open area are filled in with walls
+/// * Colonies - On open ground, in the most open areas, and as far from each other as possible
+/// * Resources - Spread over the reachable tiles, with their kind set by the biome: protein in slow ground, water
+///   where a second noise layer is wet, and sugar everywhere else
+///
+/// Every colony can reach every resource. A layout with too little reachable ground is tried again, and after
+/// [`GENERATOR_ATTEMPTS`] the world is generated without walls instead. The same seed and settings always generate
+/// the same world, which uses the seed for the rest of its randomness
+/// # Example
+/// ```
+/// # use sim::generation::{generate, get_reachable_tiles, GeneratorSettings};
+/// let settings = GeneratorSettings { colonies: 2, ..GeneratorSettings::default() };
+/// let world = generate(7, &settings);
+/// assert_eq!(world.get_colonies().len(), 2);
+///
+/// let reachable = get_reachable_tiles(&world, world.get_colonies()[0].get_position());
+/// for colony in world.get_colonies() {
+///     assert!(reachable.contains(&colony.get_position()));
+/// }
+/// for (coords, _) in world.iter_resources() {
+///     assert!(reachable.contains(&coords));
+/// }
+///
+/// let again = generate(7, &settings);
+/// assert_eq!(
+///     world.iter_resources().collect::<Vec<_>>(),
+///     again.iter_resources().collect::<Vec<_>>()
+/// );
+/// ```
+pub fn generate(seed: u64, settings: &GeneratorSettings) -> World {
+    let mut world = World::new_with_data(Vec::new(), Vec::new());
+    world.set_seed(seed);
+    let minimum_reachable =
+        (WORLD_WIDTH as f64 * WORLD_HEIGHT as f64 * GENERATOR_MINIMUM_REACHABLE_FRACTION).ceil()
+            as usize;
+    let mut layout = None;
+    for attempt in 0..GENERATOR_ATTEMPTS as u64 {
+        let mut rng = Pcg64Mcg::seed_from_u64(mix_seed(seed, &[GENERATION_KEY, attempt]));
+        let elevation = Noise::new(&mut rng);
+        let moisture = Noise::new(&mut rng);
+        for coords in all_tiles() {
+            let height = elevation.get_fractal(coords, settings.noise_scale);
+            let terrain = if height > settings.wall_threshold {
+                Terrain::Wall
+            } else if height > settings.slow_threshold {
+                Terrain::Slow
+            } else {
+                Terrain::Open
+            };
+            world.set_terrain(coords, terrain);
+        }
+        let region = get_largest_region(&world);
+        let open = region
+            .iter()
+            .filter(|coords| world.get_terrain(**coords) == Terrain::Open)
+            .count();
+        if region.len() >= minimum_reachable && open >= settings.colonies as usize {
+            layout = Some((rng, moisture, region));
+            break;
+        }
+    }
+    let (mut rng, moisture, region) = layout.unwrap_or_else(|| {
+        // No layout had enough room, so fall back to open ground everywhere
+        let mut rng = Pcg64Mcg::seed_from_u64(mix_seed(seed, &[GENERATION_KEY]));
+        let moisture = Noise::new(&mut rng);
+        for coords in all_tiles() {
+            world.set_terrain(coords, Terrain::Open);
+        }
+        (rng, moisture, all_tiles().collect())
+    });
+    for coords in all_tiles() {
+        if !region.contains(&coords) {
+            world.set_terrain(coords, Terrain::Wall);
+        }
+    }
+
+    let colonies = place_colonies(&world, &region, settings.colonies, &mut rng);
+    for position in &colonies {
+        world.add_colony(Colony::new(*position));
+    }
+
+    let mut candidates: Vec<Coordinates> = region
+        .into_iter()
+        .filter(|coords| !colonies.contains(coords))
+        .collect();
+    candidates.shuffle(&mut rng);
+    for coords in candidates.into_iter().take(settings.resources as usize) {
+        let kind = if world.get_terrain(coords) == Terrain::Slow {
+            ResourceKind::Protein
+        } else if moisture.get_fractal(coords, settings.noise_scale) > settings.wet_threshold {
+            ResourceKind::Water
+        } else {
+            ResourceKind::Sugar
+        };
+        world.set_resource(coords, Resource::from_kind(kind));
+    }
+    world
+}
+
+/// Returns every tile an ant could walk to from the given tile, including itself if it is passable
+pub fn get_reachable_tiles(world: &World, from: Coordinates) -> Vec<Coordinates> {
+    if !world.get_terrain(from).is_passable() {
+        return Vec::new();
+    }
+    let mut visited = [[false; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
+    visited[from.get_x_position_usize()][from.get_y_position_usize()] = true;
+    let mut reachable = vec![from];
+    let mut queue = VecDeque::from(vec![from]);
+    while let Some(coords) = queue.pop_front() {
+        for (x, y) in MOVE_POSSIBILITIES.iter() {
+            if let Some(next) = coords.modify(*x, *y) {
+                let seen = &mut visited[next.get_x_position_usize()][next.get_y_position_usize()];
+                if !*seen && world.get_terrain(next).is_passable() {
+                    *seen = true;
+                    reachable.push(next);
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    reachable
+}
+
+/// Returns the largest set of passable tiles that can all reach each other
+fn get_largest_region(world: &World) -> Vec<Coordinates> {
+    let mut largest: Vec<Coordinates> = Vec::new();
+    let mut checked: Vec<Coordinates> = Vec::new();
+    for coords in all_tiles() {
+        if checked.contains(&coords) || !world.get_terrain(coords).is_passable() {
+            continue;
+        }
+        let region = get_reachable_tiles(world, coords);
+        checked.extend_from_slice(&region);
+        if region.len() > largest.len() {
+            largest = region;
+        }
+    }
+    largest
+}
+
+/// Chooses where each colony goes, on open ground within the region
+///
+/// The first colony goes in one of the most open areas, and each one after as far from the others as possible
+fn place_colonies<R: Rng>(
+    world: &World,
+    region: &[Coordinates],
+    count: u8,
+    rng: &mut R,
+) -> Vec<Coordinates> {
+    let openness = |coords: &Coordinates| {
+        region
+            .iter()
+            .filter(|other| other.chebyshev_distance(*coords) <= 1)
+            .count()
+    };
+    let candidates: Vec<Coordinates> = region
+        .iter()
+        .copied()
+        .filter(|coords| world.get_terrain(*coords) == Terrain::Open)
+        .collect();
+    let most_open = candidates.iter().map(openness).max().unwrap_or(0);
+    let mut placed: Vec<Coordinates> = Vec::new();
+    for _ in 0..count {
+        let remaining = candidates.iter().filter(|coords| !placed.contains(coords));
+        let next = if placed.is_empty() {
+            remaining
+                .filter(|coords| openness(coords) == most_open)
+                .copied()
+                .collect::<Vec<_>>()
+                .choose(rng)
+                .copied()
+        } else {
+            // Ties keep the first candidate found, so the choice is still decided by the seed alone
+            let spacing = |coords: &Coordinates| {
+                placed
+                    .iter()
+                    .map(|colony| coords.euclidean_distance(*colony))
+                    .fold(f64::INFINITY, f64::min)
+            };
+            remaining
+                .copied()
+                .fold(None, |best: Option<Coordinates>, coords| match best {
+                    Some(best) if spacing(&best) >= spacing(&coords) => Some(best),
+                    _ => Some(coords),
+                })
+        };
+        match next {
+            Some(next) => placed.push(next),
+            None => break,
+        }
+    }
+    placed
+}
+
+/// Every tile in the world, a column at a time
+fn all_tiles() -> impl Iterator<Item = Coordinates> {
+    (0..WORLD_WIDTH).flat_map(|x| (0..WORLD_HEIGHT).map(move |y| Coordinates::new(x, y).unwrap()))
+}
+
+/// Two dimensional Perlin noise, from a seeded permutation table
+struct Noise {
+    permutation: Vec<u8>,
+    /// Shifts every sample away from the lattice points, where Perlin noise is always zero
+    offset: (f64, f64),
+}
+
+impl Noise {
+    fn new<R: Rng>(rng: &mut R) -> Noise {
+        let mut permutation: Vec<u8> = (0..=255).collect();
+        permutation.shuffle(rng);
+        Noise {
+            permutation,
+            offset: (rng.gen::<f64>() * 256.0, rng.gen::<f64>() * 256.0),
+        }
+    }
+
+    /// Returns two octaves of noise at the given tile, roughly between -1 and 1
+    fn get_fractal(&self, coords: Coordinates, scale: f64) -> f64 {
+        let x = coords.get_x_position_u16() as f64 * scale + self.offset.0;
+        let y = coords.get_y_position_u16() as f64 * scale + self.offset.1;
+        (self.get(x, y) + 0.5 * self.get(x * 2.0, y * 2.0)) / 1.5
+    }
+
+    /// Returns a single octave of noise at the given point
+    fn get(&self, x: f64, y: f64) -> f64 {
+        let (cell_x, cell_y) = (x.floor(), y.floor());
+        let (x, y) = (x - cell_x, y - cell_y);
+        let (cell_x, cell_y) = (cell_x as i64 & 255, cell_y as i64 & 255);
+        let hash = |dx: i64, dy: i64| {
+            let inner = self.permutation[((cell_x + dx) & 255) as usize] as i64;
+            self.permutation[((inner + cell_y + dy) & 255) as usize]
+        };
+        let fade = |t: f64| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+        let lerp = |t: f64, a: f64, b: f64| a + t * (b - a);
+        let (u, v) = (fade(x), fade(y));
+        lerp(
+            v,
+            lerp(
+                u,
+                gradient(hash(0, 0), x, y),
+                gradient(hash(1, 0), x - 1.0, y),
+            ),
+            lerp(
+                u,
+                gradient(hash(0, 1), x, y - 1.0),
+                gradient(hash(1, 1), x - 1.0, y - 1.0),
+            ),
+        )
+    }
+}
+
+/// The dot product of the given offset, with one of eight gradient directions chosen by the hash
+fn gradient(hash: u8, x: f64, y: f64) -> f64 {
+    match hash & 7 {
+        0 => x + y,
+        1 => -x + y,
+        2 => x - y,
+        3 => -x - y,
+        4 => x,
+        5 => -x,
+        6 => y,
+        _ => -y,
+    }
+}
@@ -0,0 +1,187 @@
+use crate::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use crate::pheromone::{Pheromone, PheromoneType};
+use crate::snapshot::WorldSnapshot;
+use crate::world::World;
+use crate::Coordinates;
+use enum_map::{Enum, EnumMap};
+use std::fmt;
+use std::fmt::{Debug, Display, Formatter};
+
+/// The measures that can be accumulated per tile over a run
+///
+/// * Visits - The number of ticks an ant spent on the tile, counting every ant
+/// * Trails - The number of ticks an ant spent on the tile while it held a resource pheromone
+#[derive(Copy, Clone, Eq, PartialEq, Enum)]
+pub enum HeatmapType {
+    Visits,
+    Trails,
+}
+
+impl Debug for HeatmapType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+impl Display for HeatmapType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            HeatmapType::Visits => write!(f, "Visits"),
+            HeatmapType::Trails => write!(f, "Trails"),
+        }
+    }
+}
+
+/// Per tile counts, accumulated from the snapshots, or the live world, of a run
+///
+/// Each tick is only counted once, however many times it is recorded, and recording an earlier tick (after
+/// seeking backwards through a replay, or resetting the world) starts the counts again from that tick
+pub struct Heatmaps {
+    /// The count for every tile, by type
+    counts: EnumMap<HeatmapType, Vec<u32>>,
+    /// The highest count of any tile, by type
+    maximums: EnumMap<HeatmapType, u32>,
+    /// The tick of the last snapshot that was recorded
+    last_tick: Option<u64>,
+}
+
+impl Default for Heatmaps {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Heatmaps {
+    /// Creates heatmaps with every count at zero
+    pub fn new() -> Heatmaps {
+        Heatmaps {
+            counts: EnumMap::from(|_| vec![0; WORLD_WIDTH as usize * WORLD_HEIGHT as usize]),
+            maximums: EnumMap::default(),
+            last_tick: None,
+        }
+    }
+
+    /// Adds the ants in the given snapshot to the counts
+    /// # Example
+    /// ```
+    /// # use sim::heatmap::{HeatmapType, Heatmaps};
+    /// # use sim::snapshot::WorldSnapshot;
+    /// # use sim::world::World;
+    /// let mut world = World::new_with_seed(5);
+    /// let mut heatmaps = Heatmaps::new();
+    /// for _ in 0..10 {
+    ///     world.update();
+    ///     heatmaps.record(&WorldSnapshot::new(&world));
+    /// }
+    /// // Recording the same tick again does not count it twice
+    /// heatmaps.record(&WorldSnapshot::new(&world));
+    ///
+    /// let visits: u32 = heatmaps.iter(HeatmapType::Visits).map(|(_, count)| count).sum();
+    /// assert!(visits > 0);
+    /// assert!(heatmaps.get_maximum(HeatmapType::Visits) <= 10 * world.iter_ants().count() as u32);
+    /// ```
+    pub fn record(&mut self, snapshot: &WorldSnapshot) {
+        self.record_tick(
+            snapshot.get_tick(),
+            snapshot.iter_pheromones(),
+            snapshot.iter_ants().map(|(_, ant)| ant.position),
+        );
+    }
+
+    /// Adds the ants in the given world to the counts, without taking a snapshot of it first
+    /// # Example
+    /// ```
+    /// # use sim::heatmap::{HeatmapType, Heatmaps};
+    /// # use sim::snapshot::WorldSnapshot;
+    /// # use sim::world::World;
+    /// let mut world = World::new_with_seed(5);
+    /// let (mut from_world, mut from_snapshots) = (Heatmaps::new(), Heatmaps::new());
+    /// for _ in 0..10 {
+    ///     world.update();
+    ///     from_world.record_world(&world);
+    ///     from_snapshots.record(&WorldSnapshot::new(&world));
+    /// }
+    /// assert!(from_world
+    ///     .iter(HeatmapType::Trails)
+    ///     .eq(from_snapshots.iter(HeatmapType::Trails)));
+    /// ```
+    pub fn record_world(&mut self, world: &World) {
+        self.record_tick(
+            world.get_tick(),
+            world.iter_pheromones(),
+            world.iter_ants().map(|info| info.ant.get_position()),
+        );
+    }
+
+    /// Adds the ants at the given positions to the counts, for the given tick
+    fn record_tick<'a>(
+        &mut self,
+        tick: u64,
+        pheromones: impl Iterator<Item = (Coordinates, &'a Pheromone)>,
+        ants: impl Iterator<Item = Coordinates>,
+    ) {
+        match self.last_tick {
+            Some(last) if tick == last => return,
+            Some(last) if tick < last => self.clear(),
+            _ => {}
+        }
+        self.last_tick = Some(tick);
+        let mut trails = vec![false; WORLD_WIDTH as usize * WORLD_HEIGHT as usize];
+        for (coords, pheromone) in pheromones {
+            if pheromone.pheromone_type == PheromoneType::Resource {
+                trails[index(coords)] = true;
+            }
+        }
+        for position in ants {
+            let index = index(position);
+            self.increment(HeatmapType::Visits, index);
+            if trails[index] {
+                self.increment(HeatmapType::Trails, index);
+            }
+        }
+    }
+
+    /// Sets every count back to zero
+    pub fn clear(&mut self) {
+        for (_, counts) in self.counts.iter_mut() {
+            counts.iter_mut().for_each(|count| *count = 0);
+        }
+        self.maximums = EnumMap::default();
+        self.last_tick = None;
+    }
+
+    /// Returns the count of the given type at the given position
+    pub fn get(&self, heatmap_type: HeatmapType, coords: Coordinates) -> u32 {
+        self.counts[heatmap_type][index(coords)]
+    }
+
+    /// Returns the highest count of the given type across every tile
+    pub fn get_maximum(&self, heatmap_type: HeatmapType) -> u32 {
+        self.maximums[heatmap_type]
+    }
+
+    /// Returns an iterator over every tile with a count of the given type above zero, with its position
+    pub fn iter(&self, heatmap_type: HeatmapType) -> impl Iterator<Item = (Coordinates, u32)> + '_ {
+        self.counts[heatmap_type]
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(index, count)| {
+                let x = index / WORLD_HEIGHT as usize;
+                let y = index % WORLD_HEIGHT as usize;
+                (Coordinates::new(x as u16, y as u16).unwrap(), *count)
+            })
+    }
+
+    /// Adds one to the count of the given type for a single tile
+    fn increment(&mut self, heatmap_type: HeatmapType, index: usize) {
+        let count = &mut self.counts[heatmap_type][index];
+        *count = count.saturating_add(1);
+        self.maximums[heatmap_type] = self.maximums[heatmap_type].max(*count);
+    }
+}
+
+/// The position of a tile in the count grids
+fn index(coords: Coordinates) -> usize {
+    coords.get_x_position_usize() * WORLD_HEIGHT as usize + coords.get_y_position_usize()
+}
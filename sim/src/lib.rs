@@ -1,35 +1,62 @@
 use ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use rand::Rng;
 
 use std::fmt;
 use std::fmt::{Debug, Display, Formatter};
 
 pub mod ant;
 pub mod ant_settings;
+pub mod ant_store;
+pub mod behaviour;
 pub mod colony;
+pub mod distribution;
+pub mod event;
+pub mod evolution;
+#[cfg(feature = "export")]
+pub mod export;
+pub mod generation;
+pub mod heatmap;
+pub mod map;
+pub mod palette;
 pub mod pheromone;
+pub mod pheromone_field;
+pub mod raster;
+pub mod replay;
 pub mod resource;
+pub mod snapshot;
+pub mod sweep;
+pub mod terrain;
 pub mod world;
 
 pub fn trim_f64(value: f64) -> u32 {
     (value * 1000_f64) as u32
 }
 
+/// Deterministically combines a seed with the given keys, to produce a new seed
+///
+/// Used to give every ant its own random number generator, so the outcome of a time step does not depend
+/// on the order (or thread) ants are updated in
+pub(crate) fn mix_seed(seed: u64, keys: &[u64]) -> u64 {
+    keys.iter().fold(split_mix(seed), |state, key| {
+        split_mix(state ^ split_mix(*key))
+    })
+}
+
+/// The SplitMix64 finaliser, used to scramble seeds
+fn split_mix(value: u64) -> u64 {
+    let mut value = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
 /// Used for referencing the location of a tile in the world
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Default, Eq, PartialEq)]
 pub struct Coordinates {
     x_position: u16,
     y_position: u16,
 }
 
-impl Default for Coordinates {
-    fn default() -> Self {
-        Coordinates {
-            x_position: 0,
-            y_position: 0,
-        }
-    }
-}
-
 impl Display for Coordinates {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "({}, {})", self.x_position, self.y_position)
@@ -47,7 +74,7 @@ impl Coordinates {
     /// # Examples
     /// Inside the world boundaries
     /// ```
-    /// # use Ants::sim::Coordinates;
+    /// # use sim::Coordinates;
     ///
     /// let position = Coordinates::new(5, 5);
     /// assert!(position.is_some());
@@ -59,8 +86,8 @@ impl Coordinates {
     ///
     /// When exceeding the world boundaries
     /// ```
-    /// # use Ants::sim::Coordinates;
-    /// # use Ants::ant_settings::{WORLD_WIDTH,WORLD_HEIGHT};
+    /// # use sim::Coordinates;
+    /// # use sim::ant_settings::{WORLD_WIDTH,WORLD_HEIGHT};
     ///
     /// let position = Coordinates::new(WORLD_WIDTH, WORLD_HEIGHT);
     /// assert!(position.is_none());
@@ -78,8 +105,8 @@ impl Coordinates {
     /// Creates a new random coordinate, inside the world boundaries
     /// # Example
     /// ```
-    /// # use Ants::sim::Coordinates;
-    /// # use Ants::ant_settings::{WORLD_WIDTH,WORLD_HEIGHT};
+    /// # use sim::Coordinates;
+    /// # use sim::ant_settings::{WORLD_WIDTH,WORLD_HEIGHT};
     ///
     /// let new_position = Coordinates::new_random();
     ///
@@ -87,8 +114,24 @@ impl Coordinates {
     /// assert!(new_position.get_y_position_u16() < WORLD_HEIGHT);
     /// ```
     pub fn new_random() -> Coordinates {
-        let x_position: u16 = ((rand::random::<f64>()) * (WORLD_WIDTH as f64)) as u16;
-        let y_position: u16 = ((rand::random::<f64>()) * (WORLD_HEIGHT as f64)) as u16;
+        Coordinates::new_random_from(&mut rand::thread_rng())
+    }
+
+    /// Creates a new random coordinate, inside the world boundaries, using the given random number generator
+    /// # Example
+    /// ```
+    /// # use sim::Coordinates;
+    /// # use rand::SeedableRng;
+    /// # use rand::rngs::StdRng;
+    ///
+    /// let first = Coordinates::new_random_from(&mut StdRng::seed_from_u64(7));
+    /// let second = Coordinates::new_random_from(&mut StdRng::seed_from_u64(7));
+    ///
+    /// assert_eq!(first, second);
+    /// ```
+    pub fn new_random_from<R: Rng + ?Sized>(rng: &mut R) -> Coordinates {
+        let x_position: u16 = (rng.gen::<f64>() * (WORLD_WIDTH as f64)) as u16;
+        let y_position: u16 = (rng.gen::<f64>() * (WORLD_HEIGHT as f64)) as u16;
         Coordinates {
             x_position,
             y_position,
@@ -98,7 +141,7 @@ impl Coordinates {
     /// # Examples
     /// Inside the world boundaries
     /// ```
-    /// # use Ants::sim::Coordinates;
+    /// # use sim::Coordinates;
     ///
     /// let position = Coordinates::new(5, 5).unwrap();
     /// let new_position = position.safe_modify(-2, 7);
@@ -109,8 +152,8 @@ impl Coordinates {
     ///
     /// When exceeding the world boundaries
     /// ```
-    /// # use Ants::sim::Coordinates;
-    /// # use Ants::ant_settings::{WORLD_WIDTH,WORLD_HEIGHT};
+    /// # use sim::Coordinates;
+    /// # use sim::ant_settings::{WORLD_WIDTH,WORLD_HEIGHT};
     ///
     /// let position = Coordinates::new(WORLD_WIDTH - 1, WORLD_HEIGHT - 1).unwrap();
     /// let new_position = position.safe_modify(1, 1);
@@ -121,7 +164,7 @@ impl Coordinates {
     ///
     /// When less than the world boundaries
     /// ```
-    /// # use Ants::sim::Coordinates;
+    /// # use sim::Coordinates;
     ///
     /// let position = Coordinates::new(0, 0).unwrap();
     /// let new_position = position.safe_modify(-1, -1);
@@ -161,7 +204,7 @@ impl Coordinates {
     /// # Examples
     /// Inside the world boundaries
     /// ```
-    /// # use Ants::sim::Coordinates;
+    /// # use sim::Coordinates;
     ///
     /// let position = Coordinates::new(5, 5).unwrap();
     /// let new_position = position.modify(-2, 7);
@@ -171,8 +214,8 @@ impl Coordinates {
     ///
     /// When exceeding the world boundaries
     /// ```
-    /// # use Ants::sim::Coordinates;
-    /// # use Ants::ant_settings::{WORLD_WIDTH,WORLD_HEIGHT};
+    /// # use sim::Coordinates;
+    /// # use sim::ant_settings::{WORLD_WIDTH,WORLD_HEIGHT};
     ///
     /// let position = Coordinates::new(WORLD_WIDTH - 1, WORLD_HEIGHT - 1).unwrap();
     /// let new_position = position.modify(1, 1);
@@ -182,7 +225,7 @@ impl Coordinates {
     ///
     /// When less than the world boundaries
     /// ```
-    /// # use Ants::sim::Coordinates;
+    /// # use sim::Coordinates;
     ///
     /// let position = Coordinates::new(0, 0).unwrap();
     /// let new_position = position.modify(-1, -1);
@@ -230,17 +273,84 @@ impl Coordinates {
     /// Computes the Manhattan distance, between this and the given coordinates
     /// # Example
     /// ```
-    /// # use Ants::sim::Coordinates;
+    /// # use sim::Coordinates;
     ///
     /// let position = Coordinates::new(5,5).unwrap();
-    /// let other = Coordinates::new(7,7).unwrap();
+    /// let other = Coordinates::new(7,8).unwrap();
     ///
-    /// assert_eq!(position.manhattan_distance(other), 4);
+    /// assert_eq!(position.manhattan_distance(other), 5);
     ///
     /// ```
     pub fn manhattan_distance(&self, other: Coordinates) -> u16 {
-        let x_distance = (self.x_position as i32 - other.x_position as i32).abs() as u16;
-        let y_distance = (self.x_position as i32 - other.x_position as i32).abs() as u16;
+        let x_distance = self.x_position.abs_diff(other.x_position);
+        let y_distance = self.y_position.abs_diff(other.y_position);
         x_distance + y_distance
     }
+
+    /// Computes the Chebyshev distance (the largest difference along either axis), between this and the given coordinates
+    /// # Example
+    /// ```
+    /// # use sim::Coordinates;
+    ///
+    /// let position = Coordinates::new(5,5).unwrap();
+    /// let other = Coordinates::new(7,8).unwrap();
+    ///
+    /// assert_eq!(position.chebyshev_distance(other), 3);
+    ///
+    /// ```
+    pub fn chebyshev_distance(&self, other: Coordinates) -> u16 {
+        let x_distance = self.x_position.abs_diff(other.x_position);
+        let y_distance = self.y_position.abs_diff(other.y_position);
+        x_distance.max(y_distance)
+    }
+
+    /// Computes the Euclidean (straight line) distance, between this and the given coordinates
+    /// # Example
+    /// ```
+    /// # use sim::Coordinates;
+    ///
+    /// let position = Coordinates::new(5,5).unwrap();
+    /// let other = Coordinates::new(8,9).unwrap();
+    ///
+    /// assert_eq!(position.euclidean_distance(other), 5.0);
+    ///
+    /// ```
+    pub fn euclidean_distance(&self, other: Coordinates) -> f64 {
+        let x_distance = self.x_position.abs_diff(other.x_position) as f64;
+        let y_distance = self.y_position.abs_diff(other.y_position) as f64;
+        x_distance.hypot(y_distance)
+    }
+
+    /// Computes the distance between this and the given coordinates, using the given metric
+    /// # Example
+    /// ```
+    /// # use sim::{Coordinates, DistanceMetric};
+    ///
+    /// let position = Coordinates::new(5,5).unwrap();
+    /// let other = Coordinates::new(8,9).unwrap();
+    ///
+    /// assert_eq!(position.distance(other, DistanceMetric::Manhattan), 7.0);
+    /// assert_eq!(position.distance(other, DistanceMetric::Chebyshev), 4.0);
+    /// assert_eq!(position.distance(other, DistanceMetric::Euclidean), 5.0);
+    ///
+    /// ```
+    pub fn distance(&self, other: Coordinates, metric: DistanceMetric) -> f64 {
+        match metric {
+            DistanceMetric::Manhattan => self.manhattan_distance(other) as f64,
+            DistanceMetric::Chebyshev => self.chebyshev_distance(other) as f64,
+            DistanceMetric::Euclidean => self.euclidean_distance(other),
+        }
+    }
+}
+
+/// The possible ways of measuring the distance between two tiles
+///
+/// * Manhattan - The sum of the differences along each axis
+/// * Chebyshev - The largest difference along either axis
+/// * Euclidean - The straight line distance
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DistanceMetric {
+    Manhattan,
+    Chebyshev,
+    Euclidean,
 }
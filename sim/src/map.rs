@@ -0,0 +1,270 @@
+//! Saving and loading the layout of a world, as a plain text map file
+//!
+//! Each line of a map file is a keyword followed by its values, separated by whitespace. Blank lines and lines
+//! starting with `#` are ignored
+//!
+//! * `size <width> <height>` - The size of the world, which must match `WORLD_WIDTH` and `WORLD_HEIGHT`
+//! * `seed <seed>` - The seed the world uses (optional, a random seed is used otherwise)
+//! * `colony <x> <y>` - A colony
+//! * `spawn_cost <kind> <amount>` - The nutrition of the kind taken from the stock of the colony on the last `colony`
+//!   line, to spawn each of its ants (optional, spawning is free otherwise)
+//! * `stock <kind> <amount>` - The nutrition of the kind the colony on the last `colony` line starts with
+//! * `resource <x> <y> <amount> [kind] [capacity]` - A resource with the given amount remaining, of the kind `sugar`
+//!   (the default), `protein` or `water`, which holds `capacity` when full (the amount remaining, if not given)
+//! * `wall <x> <y>` - A wall
+//! * `slow <x> <y>` - Slow ground
+//! * `policy <name> [values]` - What happens to resources over time (optional, resources are finite otherwise), one of
+//!   `finite`, `regrow <interval> <amount>`, `respawn <delay>` or `seasonal <period> <count>`
+//!
+//! Only the layout and each colony's stock are saved, so ants and pheromones are not kept
+use crate::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use crate::colony::Colony;
+use crate::resource::{Resource, ResourceKind, ResourcePolicy, RESOURCE_KINDS};
+use crate::terrain::Terrain;
+use crate::world::World;
+use crate::Coordinates;
+use std::io;
+use std::io::{BufRead, Write};
+use std::str::{FromStr, SplitWhitespace};
+
+/// Writes the layout of the given world as a map file
+///
+/// # Example
+/// ```
+/// # use sim::map;
+/// # use sim::resource::{ResourceKind, ResourcePolicy};
+/// # use sim::terrain::Terrain;
+/// # use sim::world::World;
+/// # use sim::Coordinates;
+/// let mut world = World::new_with_seed(3);
+/// world.set_terrain(Coordinates::new(1, 2).unwrap(), Terrain::Wall);
+/// world.set_resource_policy(ResourcePolicy::Respawn { delay: 50 });
+/// let colony = world.get_colony_mut(0).unwrap();
+/// colony.set_spawn_cost(ResourceKind::Protein, 2);
+/// colony.add_stock(ResourceKind::Protein, 30);
+///
+/// let mut file = Vec::new();
+/// map::save(&world, &mut file).unwrap();
+/// let loaded = map::load(file.as_slice()).unwrap();
+///
+/// assert_eq!(loaded.get_seed(), 3);
+/// assert_eq!(loaded.get_terrain(Coordinates::new(1, 2).unwrap()), Terrain::Wall);
+/// assert_eq!(loaded.iter_resources().count(), world.iter_resources().count());
+/// assert_eq!(loaded.get_colonies().len(), 1);
+/// assert_eq!(loaded.get_colonies()[0].get_spawn_cost(ResourceKind::Protein), 2);
+/// assert_eq!(loaded.get_colonies()[0].get_stock(ResourceKind::Protein), 30);
+/// assert_eq!(loaded.get_resource_policy(), world.get_resource_policy());
+/// ```
+pub fn save<W: Write>(world: &World, mut writer: W) -> io::Result<()> {
+    writeln!(writer, "# Ant simulation map")?;
+    writeln!(writer, "size {} {}", WORLD_WIDTH, WORLD_HEIGHT)?;
+    writeln!(writer, "seed {}", world.get_seed())?;
+    match world.get_resource_policy() {
+        ResourcePolicy::Finite => writeln!(writer, "policy finite")?,
+        ResourcePolicy::Regrow { interval, amount } => {
+            writeln!(writer, "policy regrow {} {}", interval, amount)?
+        }
+        ResourcePolicy::Respawn { delay } => writeln!(writer, "policy respawn {}", delay)?,
+        ResourcePolicy::Seasonal { period, count } => {
+            writeln!(writer, "policy seasonal {} {}", period, count)?
+        }
+    }
+    for colony in world.get_colonies() {
+        let position = colony.get_position();
+        writeln!(
+            writer,
+            "colony {} {}",
+            position.get_x_position_u16(),
+            position.get_y_position_u16()
+        )?;
+        for kind in &RESOURCE_KINDS {
+            let (cost, stock) = (colony.get_spawn_cost(*kind), colony.get_stock(*kind));
+            if cost > 0 {
+                writeln!(writer, "spawn_cost {} {}", kind_name(*kind), cost)?;
+            }
+            if stock > 0 {
+                writeln!(writer, "stock {} {}", kind_name(*kind), stock)?;
+            }
+        }
+    }
+    for (coords, resource) in world.iter_resources() {
+        writeln!(
+            writer,
+            "resource {} {} {} {} {}",
+            coords.get_x_position_u16(),
+            coords.get_y_position_u16(),
+            resource.get_resources_remaining(),
+            kind_name(resource.get_kind()),
+            resource.get_capacity()
+        )?;
+    }
+    for x in 0..WORLD_WIDTH {
+        for y in 0..WORLD_HEIGHT {
+            let coords = Coordinates::new(x, y).unwrap();
+            match world.get_terrain(coords) {
+                Terrain::Open => {}
+                Terrain::Slow => writeln!(writer, "slow {} {}", x, y)?,
+                Terrain::Wall => writeln!(writer, "wall {} {}", x, y)?,
+            }
+        }
+    }
+    writer.flush()
+}
+
+/// Builds a new world from a map file
+///
+/// A map that places a wall on a colony or resource, or either of them on a wall, is refused
+/// # Example
+/// ```
+/// # use sim::map;
+/// let map = "colony 4 4\nresource 6 6 20\n";
+/// assert_eq!(map::load(map.as_bytes()).unwrap().iter_resources().count(), 1);
+///
+/// let walled = "wall 6 6\nresource 6 6 20\n";
+/// assert!(map::load(walled.as_bytes()).is_err());
+/// ```
+pub fn load<R: BufRead>(reader: R) -> io::Result<World> {
+    let mut world = World::new_with_data(Vec::new(), Vec::new());
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        load_line(&mut world, line).map_err(|message| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}", index + 1, message),
+            )
+        })?;
+    }
+    Ok(world)
+}
+
+/// Applies a single line of a map file to the world
+fn load_line(world: &mut World, line: &str) -> Result<(), String> {
+    let mut values = line.split_whitespace();
+    let keyword = values.next().unwrap_or_default();
+    match keyword {
+        "size" => {
+            let size = (parse::<u16>(&mut values)?, parse::<u16>(&mut values)?);
+            if size != (WORLD_WIDTH, WORLD_HEIGHT) {
+                return Err(format!(
+                    "map is {}x{}, but the world is {}x{}",
+                    size.0, size.1, WORLD_WIDTH, WORLD_HEIGHT
+                ));
+            }
+        }
+        "seed" => world.set_seed(parse(&mut values)?),
+        "colony" => {
+            let coords = parse_coordinates(&mut values)?;
+            if !world.get_terrain(coords).is_passable() {
+                return Err(format!("colony on a wall at {}", coords));
+            }
+            world.add_colony(Colony::new(coords));
+        }
+        "spawn_cost" => {
+            let kind = parse_kind(values.next().ok_or("missing value")?)?;
+            let amount = parse(&mut values)?;
+            last_colony(world)?.set_spawn_cost(kind, amount);
+        }
+        "stock" => {
+            let kind = parse_kind(values.next().ok_or("missing value")?)?;
+            let amount = parse(&mut values)?;
+            last_colony(world)?.add_stock(kind, amount);
+        }
+        "resource" => {
+            let coords = parse_coordinates(&mut values)?;
+            let amount = parse(&mut values)?;
+            let kind = match values.next() {
+                Some(name) => parse_kind(name)?,
+                None => ResourceKind::Sugar,
+            };
+            let capacity = match values.clone().next() {
+                Some(_) => parse(&mut values)?,
+                None => amount,
+            };
+            if !world.get_terrain(coords).is_passable() {
+                return Err(format!("resource on a wall at {}", coords));
+            }
+            world.set_resource(coords, Resource::new_with_capacity(kind, amount, capacity));
+        }
+        "wall" => {
+            let coords = parse_coordinates(&mut values)?;
+            if !world.set_terrain(coords, Terrain::Wall) {
+                return Err(format!("wall on a colony or resource at {}", coords));
+            }
+        }
+        "slow" => {
+            world.set_terrain(parse_coordinates(&mut values)?, Terrain::Slow);
+        }
+        "policy" => world.set_resource_policy(parse_policy(&mut values)?),
+        _ => return Err(format!("unknown keyword '{}'", keyword)),
+    }
+    match values.next() {
+        Some(extra) => Err(format!("unexpected value '{}'", extra)),
+        None => Ok(()),
+    }
+}
+
+/// Returns the colony added by the last `colony` line
+fn last_colony(world: &mut World) -> Result<&mut Colony, String> {
+    let index = world.get_colonies().len().checked_sub(1);
+    index
+        .and_then(move |index| world.get_colony_mut(index))
+        .ok_or_else(|| "no colony before this line".to_string())
+}
+
+/// The name of a resource kind in a map file
+fn kind_name(kind: ResourceKind) -> &'static str {
+    match kind {
+        ResourceKind::Sugar => "sugar",
+        ResourceKind::Protein => "protein",
+        ResourceKind::Water => "water",
+    }
+}
+
+/// Parses the name of a resource kind
+fn parse_kind(name: &str) -> Result<ResourceKind, String> {
+    match name {
+        "sugar" => Ok(ResourceKind::Sugar),
+        "protein" => Ok(ResourceKind::Protein),
+        "water" => Ok(ResourceKind::Water),
+        _ => Err(format!("unknown resource kind '{}'", name)),
+    }
+}
+
+/// Parses the rest of a `policy` line
+fn parse_policy(values: &mut SplitWhitespace) -> Result<ResourcePolicy, String> {
+    let name = values.next().ok_or("missing value")?;
+    match name {
+        "finite" => Ok(ResourcePolicy::Finite),
+        "regrow" => Ok(ResourcePolicy::Regrow {
+            interval: parse(values)?,
+            amount: parse(values)?,
+        }),
+        "respawn" => Ok(ResourcePolicy::Respawn {
+            delay: parse(values)?,
+        }),
+        "seasonal" => Ok(ResourcePolicy::Seasonal {
+            period: parse(values)?,
+            count: parse(values)?,
+        }),
+        _ => Err(format!("unknown resource policy '{}'", name)),
+    }
+}
+
+/// Parses the next value on a line
+fn parse<T: FromStr>(values: &mut SplitWhitespace) -> Result<T, String> {
+    let value = values.next().ok_or("missing value")?;
+    value
+        .parse()
+        .map_err(|_| format!("invalid value '{}'", value))
+}
+
+/// Parses the next two values on a line as a position inside the world
+fn parse_coordinates(values: &mut SplitWhitespace) -> Result<Coordinates, String> {
+    let x = parse(values)?;
+    let y = parse(values)?;
+    Coordinates::new(x, y).ok_or_else(|| format!("({}, {}) is outside the world", x, y))
+}
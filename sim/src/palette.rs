@@ -0,0 +1,73 @@
+//! The colours every frontend draws the world with
+//!
+//! Kept in the simulation crate so the window, terminal and headless renderers all use the same scheme
+use crate::ant::AntType;
+use crate::ant_settings::MAXIMUM_PHEROMONE_STRENGTH;
+use crate::pheromone::{Pheromone, PheromoneType};
+use crate::resource::{Resource, ResourceKind};
+use crate::terrain::Terrain;
+
+/// A colour, as red, green, blue and alpha channels
+pub type Rgba = [u8; 4];
+
+/// The colour behind the world
+pub const BACKGROUND_COLOR: Rgba = [0, 0, 0, 255];
+/// The colour of empty tiles inside the world
+pub const BORDER_COLOR: Rgba = [128, 128, 128, 255];
+/// The colour of walls
+pub const WALL_COLOR: Rgba = [90, 90, 90, 255];
+/// The colour of slow ground
+pub const SLOW_COLOR: Rgba = [115, 100, 80, 255];
+/// The colour of colonies
+pub const COLONY_COLOR: Rgba = [255, 0, 0, 255];
+
+/// Returns the colour to render the given Ant Type as
+pub fn get_ant_color(ant: AntType) -> Rgba {
+    match ant {
+        AntType::Scout => [0, 0, 255, 255],
+        AntType::Worker => [50, 190, 190, 255],
+    }
+}
+
+/// Returns the color that the Pheromone should be rendered as
+///
+/// The lightness depends on the strength of the Pheromone
+pub fn get_pheromone_color(pheromone: &Pheromone) -> Rgba {
+    let color =
+        (200_f64 * ((pheromone.get_strength() as f64) / (MAXIMUM_PHEROMONE_STRENGTH as f64))) as u8
+            + 55;
+    match pheromone.pheromone_type {
+        PheromoneType::Exploration => [color, 0, color, 255],
+        PheromoneType::Resource => [color, color, color, 255],
+    }
+}
+
+/// Returns the colour that the Resource should be rendered as
+///
+/// Sugar is green, protein is orange and water is light blue. Lighter shades are less depleted, with anything above
+/// the resource's capacity drawn as if it were full
+/// # Example
+/// ```
+/// # use sim::palette::get_resource_color;
+/// # use sim::resource::{Resource, ResourceKind};
+/// let full = get_resource_color(&Resource::default());
+/// let depleted = get_resource_color(&Resource::new_with_capacity(ResourceKind::Sugar, 1, 20));
+/// assert!(full[1] > depleted[1]);
+/// ```
+pub fn get_resource_color(resource: &Resource) -> Rgba {
+    let color = (200_f64 * resource.get_percentage_remaining().min(1.0)) as u8 + 55;
+    match resource.get_kind() {
+        ResourceKind::Sugar => [0, color, 0, 255],
+        ResourceKind::Protein => [color, color / 2, 0, 255],
+        ResourceKind::Water => [color / 2, color / 2, color, 255],
+    }
+}
+
+/// Returns the colour that the terrain should be rendered as, or None for open ground, which shows the tile beneath
+pub fn get_terrain_color(terrain: Terrain) -> Option<Rgba> {
+    match terrain {
+        Terrain::Open => None,
+        Terrain::Slow => Some(SLOW_COLOR),
+        Terrain::Wall => Some(WALL_COLOR),
+    }
+}
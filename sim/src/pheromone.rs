@@ -33,12 +33,12 @@ impl Display for PheromoneType {
 
 /// This is a representation of a singular marker laid by ants
 /// Should be updated every tick, and the strength reduces by the depreciation rate
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq)]
 pub struct Pheromone {
     /// The current strength of the pheromone. Should be less than equal to the MAXIMUM_PHEROMONE_STRENGTH
     pub(crate) strength: u16,
     /// How much to reduce the strength by, per time step. Should be less than or equal to the strength
-    depreciation_rate: u16,
+    pub(crate) depreciation_rate: u16,
     /// The type of Pheromone
     pub pheromone_type: PheromoneType,
 }
@@ -175,6 +175,11 @@ impl Pheromone {
         self.strength
     }
 
+    /// How much the strength reduces by, per time step
+    pub fn get_depreciation_rate(&self) -> u16 {
+        self.depreciation_rate
+    }
+
     /*    /// Returns the color that the Pheromone should be rendered as
     ///
     /// The lightness depends on the strength of the Pheromone
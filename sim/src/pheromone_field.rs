@@ -0,0 +1,152 @@
+use crate::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use crate::pheromone::{Pheromone, PheromoneType};
+use crate::Coordinates;
+use enum_map::EnumMap;
+
+/// A container for every pheromone in the world
+///
+/// Pheromones are stored in a dense grid, for fast lookups by position, alongside an index of the active pheromones,
+/// for fast iteration. Every active pheromone appears in the index exactly once
+pub struct PheromoneField {
+    /// The pheromones on each tile, by type
+    cells: Vec<EnumMap<PheromoneType, Option<Pheromone>>>,
+    /// The position and type of every active pheromone, in the order they were created
+    active: Vec<(Coordinates, PheromoneType)>,
+}
+
+impl Default for PheromoneField {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PheromoneField {
+    /// Creates a field with no pheromones
+    pub fn new() -> PheromoneField {
+        PheromoneField {
+            cells: vec![EnumMap::default(); WORLD_WIDTH as usize * WORLD_HEIGHT as usize],
+            active: Vec::new(),
+        }
+    }
+
+    /// Returns the pheromone of the given type at the given position, if one exists
+    pub fn get(&self, coords: Coordinates, pheromone_type: PheromoneType) -> Option<&Pheromone> {
+        self.cells[index(coords)][pheromone_type].as_ref()
+    }
+
+    /// Returns every pheromone at the given position, by type
+    pub fn get_tile(&self, coords: Coordinates) -> &EnumMap<PheromoneType, Option<Pheromone>> {
+        &self.cells[index(coords)]
+    }
+
+    /// If a pheromone of the given type already exists at the given position, then refreshes it by the given amount
+    ///
+    /// Otherwise, creates a new default pheromone of the given type at the given position
+    ///
+    /// Returns true if a new pheromone was created
+    /// # Example
+    /// ```
+    /// # use sim::pheromone::PheromoneType;
+    /// # use sim::pheromone_field::PheromoneField;
+    /// # use sim::Coordinates;
+    /// let mut field = PheromoneField::new();
+    /// let coords = Coordinates::new(3, 4).unwrap();
+    ///
+    /// assert!(field.deposit(coords, PheromoneType::Resource, 10));
+    /// assert!(!field.deposit(coords, PheromoneType::Resource, 10));
+    /// assert_eq!(field.len(), 1);
+    /// ```
+    pub fn deposit(
+        &mut self,
+        coords: Coordinates,
+        pheromone_type: PheromoneType,
+        refresh_amount: u16,
+    ) -> bool {
+        let cell = &mut self.cells[index(coords)][pheromone_type];
+        if let Some(pheromone) = cell {
+            pheromone.refresh(refresh_amount);
+            false
+        } else {
+            *cell = Some(Pheromone::default(pheromone_type));
+            self.active.push((coords, pheromone_type));
+            true
+        }
+    }
+
+    /// Places the given pheromone at the given position, replacing any pheromone of the same type
+    pub fn insert(&mut self, coords: Coordinates, pheromone: Pheromone) {
+        let cell = &mut self.cells[index(coords)][pheromone.pheromone_type];
+        if cell.is_none() {
+            self.active.push((coords, pheromone.pheromone_type));
+        }
+        *cell = Some(pheromone);
+    }
+
+    /// Removes the pheromone of the given type at the given position, returning it if it existed
+    pub fn remove(
+        &mut self,
+        coords: Coordinates,
+        pheromone_type: PheromoneType,
+    ) -> Option<Pheromone> {
+        let pheromone = self.cells[index(coords)][pheromone_type].take()?;
+        self.active
+            .retain(|entry| *entry != (coords, pheromone_type));
+        Some(pheromone)
+    }
+
+    /// Reduces the strength of every pheromone for one time step, removing any that have expired
+    ///
+    /// Returns the position and type of every pheromone that expired
+    /// # Example
+    /// ```
+    /// # use sim::pheromone::{Pheromone, PheromoneType};
+    /// # use sim::pheromone_field::PheromoneField;
+    /// # use sim::Coordinates;
+    /// let mut field = PheromoneField::new();
+    /// let coords = Coordinates::new(3, 4).unwrap();
+    /// field.insert(coords, Pheromone::new(10, 6, PheromoneType::Exploration).unwrap());
+    ///
+    /// assert!(field.decay().is_empty());
+    /// assert_eq!(field.decay(), vec![(coords, PheromoneType::Exploration)]);
+    /// assert!(field.is_empty());
+    /// ```
+    pub fn decay(&mut self) -> Vec<(Coordinates, PheromoneType)> {
+        let cells = &mut self.cells;
+        let mut expired = Vec::new();
+        self.active.retain(|(coords, pheromone_type)| {
+            let cell = &mut cells[index(*coords)][*pheromone_type];
+            let retain = cell.as_mut().is_some_and(|pheromone| pheromone.update());
+            if !retain {
+                *cell = None;
+                expired.push((*coords, *pheromone_type));
+            }
+            retain
+        });
+        expired
+    }
+
+    /// Returns an iterator over every active pheromone, with its position, in the order they were created
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinates, &Pheromone)> + '_ {
+        self.active
+            .iter()
+            .filter_map(move |(coords, pheromone_type)| {
+                self.get(*coords, *pheromone_type)
+                    .map(|pheromone| (*coords, pheromone))
+            })
+    }
+
+    /// The number of active pheromones
+    pub fn len(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Returns true if there are no active pheromones
+    pub fn is_empty(&self) -> bool {
+        self.active.is_empty()
+    }
+}
+
+/// The position of the given coordinates in the grid
+fn index(coords: Coordinates) -> usize {
+    coords.get_x_position_usize() * WORLD_HEIGHT as usize + coords.get_y_position_usize()
+}
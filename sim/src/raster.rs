@@ -0,0 +1,112 @@
+//! A software renderer, for drawing the world without a window or GPU
+use crate::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use crate::palette::{
+    get_ant_color, get_pheromone_color, get_resource_color, get_terrain_color, Rgba, BORDER_COLOR,
+    COLONY_COLOR,
+};
+use crate::snapshot::WorldSnapshot;
+use crate::world::World;
+use crate::Coordinates;
+
+/// An image of the world, as rows of RGBA pixels from the top left
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Frame {
+    /// Draws the given world, with each tile as a square of `tile_size` pixels
+    pub fn new(world: &World, tile_size: u32) -> Frame {
+        Frame::from_snapshot(&WorldSnapshot::new(world), tile_size)
+    }
+
+    /// Draws the given snapshot, with each tile as a square of `tile_size` pixels
+    ///
+    /// Layers are drawn in the same order as the window renderer: terrain, pheromones, ants, colonies and then
+    /// resources on top
+    /// # Example
+    /// ```
+    /// # use sim::palette::COLONY_COLOR;
+    /// # use sim::raster::Frame;
+    /// # use sim::world::World;
+    /// let world = World::new_with_seed(1);
+    /// let frame = Frame::new(&world, 4);
+    /// assert_eq!(frame.get_pixels().len(), (frame.get_width() * frame.get_height() * 4) as usize);
+    ///
+    /// let colony = world.get_colonies()[0].get_position();
+    /// let pixel = frame.get_pixel(
+    ///     colony.get_x_position_u16() as u32 * 4,
+    ///     colony.get_y_position_u16() as u32 * 4,
+    /// );
+    /// assert_eq!(pixel, COLONY_COLOR);
+    /// ```
+    pub fn from_snapshot(snapshot: &WorldSnapshot, tile_size: u32) -> Frame {
+        let tile_size = tile_size.max(1);
+        let width = WORLD_WIDTH as u32 * tile_size;
+        let height = WORLD_HEIGHT as u32 * tile_size;
+        let mut frame = Frame {
+            width,
+            height,
+            pixels: BORDER_COLOR
+                .iter()
+                .copied()
+                .cycle()
+                .take((width * height * 4) as usize)
+                .collect(),
+        };
+        for (coords, terrain) in snapshot.iter_terrain() {
+            if let Some(color) = get_terrain_color(terrain) {
+                frame.fill_tile(coords, tile_size, color);
+            }
+        }
+        for (coords, pheromone) in snapshot.iter_pheromones() {
+            frame.fill_tile(coords, tile_size, get_pheromone_color(pheromone));
+        }
+        for (_colony, ant) in snapshot.iter_ants() {
+            frame.fill_tile(ant.position, tile_size, get_ant_color(ant.ant_type));
+        }
+        for colony in snapshot.get_colonies() {
+            frame.fill_tile(colony.position, tile_size, COLONY_COLOR);
+        }
+        for (coords, resource) in snapshot.iter_resources() {
+            frame.fill_tile(coords, tile_size, get_resource_color(resource));
+        }
+        frame
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns every pixel, as rows of RGBA values from the top left
+    pub fn get_pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Returns the colour of a single pixel
+    pub fn get_pixel(&self, x: u32, y: u32) -> Rgba {
+        let start = ((y * self.width + x) * 4) as usize;
+        let mut pixel = [0; 4];
+        pixel.copy_from_slice(&self.pixels[start..start + 4]);
+        pixel
+    }
+
+    /// Colours every pixel of a single tile
+    fn fill_tile(&mut self, coords: Coordinates, tile_size: u32, color: Rgba) {
+        let left = coords.get_x_position_u16() as u32 * tile_size;
+        let top = coords.get_y_position_u16() as u32 * tile_size;
+        for y in top..top + tile_size {
+            let start = ((y * self.width + left) * 4) as usize;
+            let end = start + (tile_size * 4) as usize;
+            for pixel in self.pixels[start..end].chunks_exact_mut(4) {
+                pixel.copy_from_slice(&color);
+            }
+        }
+    }
+}
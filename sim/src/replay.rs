@@ -0,0 +1,843 @@
+//! Recording and playback of a simulation, without re-running it
+//!
+//! A replay is a header, followed by one frame per recorded time step. Every frame is either a keyframe (a full
+//! [`WorldSnapshot`]), or a delta from the previous frame. Deltas store ant moves as a single byte where possible,
+//! and only store pheromones that differ from what decaying the previous frame would give, so they stay small.
+//! Keyframes are written at a fixed interval, so any frame can be rebuilt by finding the nearest keyframe before it,
+//! and applying the deltas after it. Deltas can only add colonies, so a keyframe is also written whenever a colony is
+//! removed
+//!
+//! All numbers are stored little endian
+use crate::ant::{AntType, ANT_TYPES};
+use crate::ant_store::AntId;
+use crate::pheromone::{Pheromone, PheromoneType};
+use crate::resource::{Resource, ResourceKind};
+use crate::snapshot::{pheromone_key, position_key, AntSnapshot, ColonySnapshot, WorldSnapshot};
+use crate::terrain::Terrain;
+use crate::world::World;
+use crate::Coordinates;
+use std::cmp::Ordering;
+use std::io;
+use std::io::{Read, Write};
+
+/// The bytes every replay starts with
+const MAGIC: &[u8; 4] = b"ANTR";
+/// The version of the format, increased whenever it changes
+const VERSION: u8 = 5;
+
+/// Marks a frame containing a full snapshot
+const KEYFRAME: u8 = 0;
+/// Marks a frame containing the changes from the previous frame
+const DELTA: u8 = 1;
+
+/// Marks an entry that was removed since the previous frame
+const REMOVED: u8 = 0;
+/// Marks an entry that was added or changed since the previous frame
+const SET: u8 = 1;
+
+/// Marks an ant move that was not to an adjacent tile, and is followed by the new position
+const JUMP: u8 = u8::MAX;
+
+/// Writes a replay of a world, one frame at a time
+///
+/// # Example
+/// ```
+/// # use sim::replay::{Recorder, Replay};
+/// # use sim::world::World;
+/// let mut world = World::new_with_seed(11);
+/// let mut recorder = Recorder::new(&world, Vec::new(), 10).unwrap();
+/// for _ in 0..25 {
+///     world.update();
+///     recorder.record(&world).unwrap();
+/// }
+/// let data = recorder.finish().unwrap();
+///
+/// let replay = Replay::read(data.as_slice()).unwrap();
+/// assert_eq!(replay.len(), 26);
+/// assert_eq!(replay.get_seed(), 11);
+/// ```
+pub struct Recorder<W: Write> {
+    writer: W,
+    /// The number of frames between each keyframe
+    keyframe_interval: u64,
+    /// The number of frames written so far
+    frames: u64,
+    /// The snapshot written in the last frame
+    previous: WorldSnapshot,
+}
+
+impl<W: Write> Recorder<W> {
+    /// Starts a replay of the given world, writing its current state as the first frame
+    ///
+    /// A keyframe interval of zero is treated as one, so every frame is a keyframe
+    pub fn new(world: &World, mut writer: W, keyframe_interval: u32) -> io::Result<Recorder<W>> {
+        let keyframe_interval = keyframe_interval.max(1);
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(MAGIC);
+        buffer.push(VERSION);
+        write_u64(&mut buffer, world.get_seed());
+        write_u32(&mut buffer, keyframe_interval);
+
+        let snapshot = WorldSnapshot::new(world);
+        encode_keyframe(&mut buffer, &snapshot);
+        writer.write_all(&buffer)?;
+        Ok(Recorder {
+            writer,
+            keyframe_interval: keyframe_interval as u64,
+            frames: 1,
+            previous: snapshot,
+        })
+    }
+
+    /// Records the current state of the world as the next frame
+    ///
+    /// Usually called after every [`World::update`], but any frames can be skipped
+    pub fn record(&mut self, world: &World) -> io::Result<()> {
+        let snapshot = WorldSnapshot::new(world);
+        let mut buffer = Vec::new();
+        if self.frames.is_multiple_of(self.keyframe_interval)
+            || !colonies_extend(&self.previous, &snapshot)
+        {
+            encode_keyframe(&mut buffer, &snapshot);
+        } else {
+            encode_delta(&mut buffer, &self.previous, &snapshot);
+        }
+        self.writer.write_all(&buffer)?;
+        self.previous = snapshot;
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Flushes the replay, and returns the underlying writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// A replay read into memory, that any frame can be rebuilt from
+///
+/// Frames are kept encoded, so a replay takes up about as much memory as its file
+pub struct Replay {
+    seed: u64,
+    keyframe_interval: u32,
+    data: Vec<u8>,
+    /// The offset of every frame in the data
+    frames: Vec<usize>,
+    /// The index of every keyframe, in ascending order
+    keyframes: Vec<usize>,
+}
+
+impl Replay {
+    /// Reads a whole replay, checking that every frame is valid
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Replay> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        let mut cursor = Cursor::new(&data);
+        if cursor.read_bytes(MAGIC.len())? != MAGIC {
+            return Err(invalid("not a replay file"));
+        }
+        if cursor.read_u8()? != VERSION {
+            return Err(invalid("unsupported replay version"));
+        }
+        let seed = cursor.read_u64()?;
+        let keyframe_interval = cursor.read_u32()?;
+
+        let mut frames = Vec::new();
+        let mut keyframes = Vec::new();
+        let mut snapshot = WorldSnapshot::default();
+        while !cursor.is_empty() {
+            if frames.is_empty() && data[cursor.position] != KEYFRAME {
+                return Err(invalid("replay does not start with a keyframe"));
+            }
+            if data[cursor.position] == KEYFRAME {
+                keyframes.push(frames.len());
+            }
+            frames.push(cursor.position);
+            decode_frame(&mut cursor, &mut snapshot)?;
+        }
+        Ok(Replay {
+            seed,
+            keyframe_interval,
+            data,
+            frames,
+            keyframes,
+        })
+    }
+
+    /// The seed of the recorded world
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The number of frames between each keyframe
+    pub fn get_keyframe_interval(&self) -> u32 {
+        self.keyframe_interval
+    }
+
+    /// The number of recorded frames
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns true if no frames were recorded
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Rebuilds the frame at the given index, or None if it is out of range
+    pub fn get_snapshot(&self, index: usize) -> Option<WorldSnapshot> {
+        if index >= self.len() {
+            return None;
+        }
+        let keyframe = match self.keyframes.binary_search(&index) {
+            Ok(position) => self.keyframes[position],
+            Err(position) => self.keyframes[position - 1],
+        };
+        let mut snapshot = WorldSnapshot::default();
+        for frame in keyframe..=index {
+            self.apply_frame(frame, &mut snapshot);
+        }
+        Some(snapshot)
+    }
+
+    /// Applies the frame at the given index on top of the previous frame
+    fn apply_frame(&self, index: usize, snapshot: &mut WorldSnapshot) {
+        let mut cursor = Cursor::new(&self.data);
+        cursor.position = self.frames[index];
+        decode_frame(&mut cursor, snapshot).expect("frames are checked when the replay is read");
+    }
+}
+
+/// Plays back a replay, one frame at a time in either direction
+///
+/// # Example
+/// ```
+/// # use sim::replay::{Recorder, Replay, ReplayPlayer};
+/// # use sim::snapshot::WorldSnapshot;
+/// # use sim::world::World;
+/// let mut world = World::new_with_seed(5);
+/// let mut recorder = Recorder::new(&world, Vec::new(), 4).unwrap();
+/// let mut snapshots = vec![WorldSnapshot::new(&world)];
+/// for _ in 0..10 {
+///     world.update();
+///     recorder.record(&world).unwrap();
+///     snapshots.push(WorldSnapshot::new(&world));
+/// }
+/// let data = recorder.finish().unwrap();
+/// let mut player = ReplayPlayer::new(Replay::read(data.as_slice()).unwrap()).unwrap();
+///
+/// while player.step_forward() {}
+/// assert_eq!(player.get_snapshot(), &snapshots[10]);
+///
+/// assert!(player.step_backward());
+/// assert_eq!(player.get_snapshot(), &snapshots[9]);
+///
+/// assert!(player.seek(2));
+/// assert_eq!(player.get_snapshot(), &snapshots[2]);
+/// ```
+pub struct ReplayPlayer {
+    replay: Replay,
+    /// The index of the current frame
+    index: usize,
+    /// The current frame
+    snapshot: WorldSnapshot,
+}
+
+impl ReplayPlayer {
+    /// Starts playback from the first frame, or returns None if the replay has no frames
+    pub fn new(replay: Replay) -> Option<ReplayPlayer> {
+        let snapshot = replay.get_snapshot(0)?;
+        Some(ReplayPlayer {
+            replay,
+            index: 0,
+            snapshot,
+        })
+    }
+
+    /// The replay being played
+    pub fn get_replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// The index of the current frame
+    pub fn get_index(&self) -> usize {
+        self.index
+    }
+
+    /// The current frame
+    pub fn get_snapshot(&self) -> &WorldSnapshot {
+        &self.snapshot
+    }
+
+    /// Moves to the next frame, returning false if already at the last frame
+    pub fn step_forward(&mut self) -> bool {
+        if self.index + 1 >= self.replay.len() {
+            return false;
+        }
+        self.index += 1;
+        self.replay.apply_frame(self.index, &mut self.snapshot);
+        true
+    }
+
+    /// Moves to the previous frame, returning false if already at the first frame
+    ///
+    /// The frame is rebuilt from the nearest keyframe before it
+    pub fn step_backward(&mut self) -> bool {
+        self.index > 0 && self.seek(self.index - 1)
+    }
+
+    /// Moves to the frame at the given index, returning false if it is out of range
+    pub fn seek(&mut self, index: usize) -> bool {
+        match self.replay.get_snapshot(index) {
+            Some(snapshot) => {
+                self.index = index;
+                self.snapshot = snapshot;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Writes a frame containing the whole snapshot
+fn encode_keyframe(buffer: &mut Vec<u8>, snapshot: &WorldSnapshot) {
+    buffer.push(KEYFRAME);
+    write_u64(buffer, snapshot.tick);
+    write_u32(buffer, snapshot.colonies.len() as u32);
+    for colony in &snapshot.colonies {
+        write_coordinates(buffer, colony.position);
+        write_u32(buffer, colony.ants.len() as u32);
+        for ant in &colony.ants {
+            write_ant(buffer, ant);
+        }
+    }
+    write_u32(buffer, snapshot.resources.len() as u32);
+    for (coords, resource) in &snapshot.resources {
+        write_coordinates(buffer, *coords);
+        write_resource(buffer, resource);
+    }
+    write_u32(buffer, snapshot.pheromones.len() as u32);
+    for (coords, pheromone) in &snapshot.pheromones {
+        write_coordinates(buffer, *coords);
+        write_pheromone(buffer, pheromone);
+    }
+    write_u32(buffer, snapshot.terrain.len() as u32);
+    for (coords, terrain) in &snapshot.terrain {
+        write_coordinates(buffer, *coords);
+        buffer.push(terrain_to_u8(*terrain));
+    }
+}
+
+/// Returns true if the current snapshot has every colony of the previous one, at the same index, so a delta can
+/// describe it
+fn colonies_extend(previous: &WorldSnapshot, current: &WorldSnapshot) -> bool {
+    previous.colonies.len() <= current.colonies.len()
+        && previous
+            .colonies
+            .iter()
+            .zip(&current.colonies)
+            .all(|(previous, current)| previous.position == current.position)
+}
+
+/// Writes a frame containing the changes between the two snapshots
+fn encode_delta(buffer: &mut Vec<u8>, previous: &WorldSnapshot, current: &WorldSnapshot) {
+    buffer.push(DELTA);
+    write_u64(buffer, current.tick);
+
+    write_u32(buffer, current.colonies.len() as u32);
+    for colony in current.colonies.iter().skip(previous.colonies.len()) {
+        write_coordinates(buffer, colony.position);
+    }
+    for (index, colony) in current.colonies.iter().enumerate() {
+        let previous_ants = previous
+            .colonies
+            .get(index)
+            .map_or(&[][..], |colony| &colony.ants[..]);
+        encode_ants(buffer, previous_ants, &colony.ants);
+    }
+
+    let changes = diff(&previous.resources, &current.resources, |(coords, _)| {
+        position_key(*coords)
+    });
+    write_u32(buffer, changes.len() as u32);
+    for change in changes {
+        match change {
+            Change::Set(coords, resource) => {
+                write_coordinates(buffer, coords);
+                buffer.push(SET);
+                write_resource(buffer, &resource);
+            }
+            Change::Removed(coords, _) => {
+                write_coordinates(buffer, coords);
+                buffer.push(REMOVED);
+            }
+        }
+    }
+
+    let changes = diff(&previous.terrain, &current.terrain, |(coords, _)| {
+        position_key(*coords)
+    });
+    write_u32(buffer, changes.len() as u32);
+    for change in changes {
+        match change {
+            Change::Set(coords, terrain) => {
+                write_coordinates(buffer, coords);
+                buffer.push(SET);
+                buffer.push(terrain_to_u8(terrain));
+            }
+            Change::Removed(coords, _) => {
+                write_coordinates(buffer, coords);
+                buffer.push(REMOVED);
+            }
+        }
+    }
+
+    let mut predicted = previous.pheromones.clone();
+    decay(&mut predicted);
+    let changes = diff(&predicted, &current.pheromones, |(coords, pheromone)| {
+        pheromone_key(*coords, pheromone.pheromone_type)
+    });
+    write_u32(buffer, changes.len() as u32);
+    for change in changes {
+        match change {
+            Change::Set(coords, pheromone) => {
+                write_coordinates(buffer, coords);
+                buffer.push(SET);
+                write_pheromone(buffer, &pheromone);
+            }
+            Change::Removed(coords, pheromone) => {
+                write_coordinates(buffer, coords);
+                buffer.push(REMOVED);
+                write_pheromone_type(buffer, pheromone.pheromone_type);
+            }
+        }
+    }
+}
+
+/// Writes the ants removed from a colony, the move of every remaining ant, and the ants spawned
+fn encode_ants(buffer: &mut Vec<u8>, previous: &[AntSnapshot], current: &[AntSnapshot]) {
+    let mut removed = Vec::new();
+    let mut moves = Vec::new();
+    let mut spawned = Vec::new();
+    let (mut old, mut new) = (0, 0);
+    while old < previous.len() || new < current.len() {
+        match (previous.get(old), current.get(new)) {
+            (Some(before), Some(after)) if before.id == after.id => {
+                write_move(&mut moves, before.position, after.position);
+                old += 1;
+                new += 1;
+            }
+            (Some(before), after) if after.is_none_or(|after| before.id < after.id) => {
+                removed.push(before.id);
+                old += 1;
+            }
+            (_, Some(after)) => {
+                spawned.push(after);
+                new += 1;
+            }
+            (_, None) => unreachable!("the loop ends once both lists are exhausted"),
+        }
+    }
+    write_u32(buffer, removed.len() as u32);
+    for id in removed {
+        write_u64(buffer, id.get_id());
+    }
+    buffer.extend_from_slice(&moves);
+    write_u32(buffer, spawned.len() as u32);
+    for ant in spawned {
+        write_ant(buffer, ant);
+    }
+}
+
+/// Reads a single frame, replacing the snapshot if it is a keyframe, or applying it to the snapshot if it is a delta
+fn decode_frame(cursor: &mut Cursor, snapshot: &mut WorldSnapshot) -> io::Result<()> {
+    match cursor.read_u8()? {
+        KEYFRAME => *snapshot = decode_keyframe(cursor)?,
+        DELTA => decode_delta(cursor, snapshot)?,
+        _ => return Err(invalid("unknown frame type")),
+    }
+    Ok(())
+}
+
+/// Reads the contents of a keyframe
+fn decode_keyframe(cursor: &mut Cursor) -> io::Result<WorldSnapshot> {
+    let tick = cursor.read_u64()?;
+    let mut colonies = Vec::new();
+    for _ in 0..cursor.read_u32()? {
+        let position = cursor.read_coordinates()?;
+        let mut ants = Vec::new();
+        for _ in 0..cursor.read_u32()? {
+            ants.push(cursor.read_ant()?);
+        }
+        colonies.push(ColonySnapshot { position, ants });
+    }
+    let mut resources = Vec::new();
+    for _ in 0..cursor.read_u32()? {
+        let coords = cursor.read_coordinates()?;
+        resources.push((coords, cursor.read_resource()?));
+    }
+    let mut pheromones = Vec::new();
+    for _ in 0..cursor.read_u32()? {
+        let coords = cursor.read_coordinates()?;
+        pheromones.push((coords, cursor.read_pheromone()?));
+    }
+    let mut terrain = Vec::new();
+    for _ in 0..cursor.read_u32()? {
+        let coords = cursor.read_coordinates()?;
+        terrain.push((coords, cursor.read_terrain()?));
+    }
+    Ok(WorldSnapshot {
+        tick,
+        colonies,
+        resources,
+        pheromones,
+        terrain,
+    })
+}
+
+/// Reads the contents of a delta, and applies it to the snapshot
+fn decode_delta(cursor: &mut Cursor, snapshot: &mut WorldSnapshot) -> io::Result<()> {
+    snapshot.tick = cursor.read_u64()?;
+
+    let colony_count = cursor.read_u32()? as usize;
+    snapshot.colonies.truncate(colony_count);
+    while snapshot.colonies.len() < colony_count {
+        snapshot.colonies.push(ColonySnapshot {
+            position: cursor.read_coordinates()?,
+            ants: Vec::new(),
+        });
+    }
+    for colony in &mut snapshot.colonies {
+        let mut removed = Vec::new();
+        for _ in 0..cursor.read_u32()? {
+            removed.push(AntId(cursor.read_u64()?));
+        }
+        colony.ants.retain(|ant| !removed.contains(&ant.id));
+        for ant in &mut colony.ants {
+            ant.position = cursor.read_move(ant.position)?;
+        }
+        for _ in 0..cursor.read_u32()? {
+            colony.ants.push(cursor.read_ant()?);
+        }
+        colony.ants.sort_by_key(|ant| ant.id);
+    }
+
+    for _ in 0..cursor.read_u32()? {
+        let coords = cursor.read_coordinates()?;
+        let resource = match cursor.read_u8()? {
+            SET => Some(cursor.read_resource()?),
+            REMOVED => None,
+            _ => return Err(invalid("unknown resource change")),
+        };
+        apply_change(
+            &mut snapshot.resources,
+            |(coords, _)| position_key(*coords),
+            position_key(coords),
+            resource.map(|resource| (coords, resource)),
+        );
+    }
+
+    for _ in 0..cursor.read_u32()? {
+        let coords = cursor.read_coordinates()?;
+        let terrain = match cursor.read_u8()? {
+            SET => Some(cursor.read_terrain()?),
+            REMOVED => None,
+            _ => return Err(invalid("unknown terrain change")),
+        };
+        apply_change(
+            &mut snapshot.terrain,
+            |(coords, _)| position_key(*coords),
+            position_key(coords),
+            terrain.map(|terrain| (coords, terrain)),
+        );
+    }
+
+    decay(&mut snapshot.pheromones);
+    for _ in 0..cursor.read_u32()? {
+        let coords = cursor.read_coordinates()?;
+        let (pheromone_type, pheromone) = match cursor.read_u8()? {
+            SET => {
+                let pheromone = cursor.read_pheromone()?;
+                (pheromone.pheromone_type, Some(pheromone))
+            }
+            REMOVED => (cursor.read_pheromone_type()?, None),
+            _ => return Err(invalid("unknown pheromone change")),
+        };
+        apply_change(
+            &mut snapshot.pheromones,
+            |(coords, pheromone)| pheromone_key(*coords, pheromone.pheromone_type),
+            pheromone_key(coords, pheromone_type),
+            pheromone.map(|pheromone| (coords, pheromone)),
+        );
+    }
+    Ok(())
+}
+
+/// Decays every pheromone for one time step, the same way the world does
+fn decay(pheromones: &mut Vec<(Coordinates, Pheromone)>) {
+    pheromones.retain_mut(|(_, pheromone)| pheromone.update());
+}
+
+/// A difference between two lists of entries
+enum Change<T> {
+    /// The entry was added or changed, with its new value
+    Set(Coordinates, T),
+    /// The entry was removed, with its old value
+    Removed(Coordinates, T),
+}
+
+/// Finds every entry that differs between two lists sorted by the same key
+fn diff<T, K, F>(
+    previous: &[(Coordinates, T)],
+    current: &[(Coordinates, T)],
+    key: F,
+) -> Vec<Change<T>>
+where
+    T: Copy + PartialEq,
+    K: Ord,
+    F: Fn(&(Coordinates, T)) -> K,
+{
+    let mut changes = Vec::new();
+    let (mut old, mut new) = (0, 0);
+    while old < previous.len() || new < current.len() {
+        let order = match (previous.get(old), current.get(new)) {
+            (Some(before), Some(after)) => key(before).cmp(&key(after)),
+            (Some(_), None) => Ordering::Less,
+            _ => Ordering::Greater,
+        };
+        match order {
+            Ordering::Equal => {
+                if previous[old] != current[new] {
+                    changes.push(Change::Set(current[new].0, current[new].1));
+                }
+                old += 1;
+                new += 1;
+            }
+            Ordering::Less => {
+                changes.push(Change::Removed(previous[old].0, previous[old].1));
+                old += 1;
+            }
+            Ordering::Greater => {
+                changes.push(Change::Set(current[new].0, current[new].1));
+                new += 1;
+            }
+        }
+    }
+    changes
+}
+
+/// Replaces or removes the entry with the given key, in a list sorted by that key
+fn apply_change<T, K, F>(
+    entries: &mut Vec<(Coordinates, T)>,
+    key: F,
+    target: K,
+    entry: Option<(Coordinates, T)>,
+) where
+    K: Ord,
+    F: Fn(&(Coordinates, T)) -> K,
+{
+    match (
+        entries.binary_search_by(|existing| key(existing).cmp(&target)),
+        entry,
+    ) {
+        (Ok(position), Some(entry)) => entries[position] = entry,
+        (Ok(position), None) => {
+            entries.remove(position);
+        }
+        (Err(position), Some(entry)) => entries.insert(position, entry),
+        (Err(_), None) => {}
+    }
+}
+
+fn write_u32(buffer: &mut Vec<u8>, value: u32) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_u64(buffer: &mut Vec<u8>, value: u64) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_coordinates(buffer: &mut Vec<u8>, coords: Coordinates) {
+    buffer.extend_from_slice(&coords.get_x_position_u16().to_le_bytes());
+    buffer.extend_from_slice(&coords.get_y_position_u16().to_le_bytes());
+}
+
+fn write_ant(buffer: &mut Vec<u8>, ant: &AntSnapshot) {
+    write_u64(buffer, ant.id.get_id());
+    let ant_type = ANT_TYPES
+        .iter()
+        .position(|ant_type| *ant_type == ant.ant_type)
+        .expect("every ant type is in ANT_TYPES");
+    buffer.push(ant_type as u8);
+    write_coordinates(buffer, ant.position);
+}
+
+fn write_pheromone_type(buffer: &mut Vec<u8>, pheromone_type: PheromoneType) {
+    buffer.push(pheromone_key(Coordinates::default(), pheromone_type).2);
+}
+
+fn write_pheromone(buffer: &mut Vec<u8>, pheromone: &Pheromone) {
+    write_pheromone_type(buffer, pheromone.pheromone_type);
+    buffer.extend_from_slice(&pheromone.strength.to_le_bytes());
+    buffer.extend_from_slice(&pheromone.depreciation_rate.to_le_bytes());
+}
+
+fn write_resource(buffer: &mut Vec<u8>, resource: &Resource) {
+    buffer.push(match resource.kind {
+        ResourceKind::Sugar => 0,
+        ResourceKind::Protein => 1,
+        ResourceKind::Water => 2,
+    });
+    write_u32(buffer, resource.resources_remaining);
+    write_u32(buffer, resource.capacity);
+}
+
+fn terrain_to_u8(terrain: Terrain) -> u8 {
+    match terrain {
+        Terrain::Open => 0,
+        Terrain::Wall => 1,
+        Terrain::Slow => 2,
+    }
+}
+
+/// Writes a move to an adjacent tile (or staying still) as a single byte, and any other move as a jump
+fn write_move(buffer: &mut Vec<u8>, from: Coordinates, to: Coordinates) {
+    let x_amount = to.get_x_position_u16() as i32 - from.get_x_position_u16() as i32;
+    let y_amount = to.get_y_position_u16() as i32 - from.get_y_position_u16() as i32;
+    if x_amount.abs() <= 1 && y_amount.abs() <= 1 {
+        buffer.push(((x_amount + 1) * 3 + (y_amount + 1)) as u8);
+    } else {
+        buffer.push(JUMP);
+        write_coordinates(buffer, to);
+    }
+}
+
+/// Reads values from an in memory replay
+struct Cursor<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8]) -> Cursor<'a> {
+        Cursor { data, position: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.position >= self.data.len()
+    }
+
+    fn read_bytes(&mut self, length: usize) -> io::Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.position..self.position + length)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "replay ended early"))?;
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> io::Result<u8> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> io::Result<u16> {
+        let mut bytes = [0; 2];
+        bytes.copy_from_slice(self.read_bytes(2)?);
+        Ok(u16::from_le_bytes(bytes))
+    }
+
+    fn read_u32(&mut self) -> io::Result<u32> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.read_bytes(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn read_u64(&mut self) -> io::Result<u64> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.read_bytes(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn read_coordinates(&mut self) -> io::Result<Coordinates> {
+        let x_position = self.read_u16()?;
+        let y_position = self.read_u16()?;
+        Coordinates::new(x_position, y_position)
+            .ok_or_else(|| invalid("position is outside the world"))
+    }
+
+    fn read_ant(&mut self) -> io::Result<AntSnapshot> {
+        let id = AntId(self.read_u64()?);
+        let ant_type: AntType = *ANT_TYPES
+            .get(self.read_u8()? as usize)
+            .ok_or_else(|| invalid("unknown ant type"))?;
+        let position = self.read_coordinates()?;
+        Ok(AntSnapshot {
+            id,
+            ant_type,
+            position,
+        })
+    }
+
+    fn read_resource(&mut self) -> io::Result<Resource> {
+        let kind = match self.read_u8()? {
+            0 => ResourceKind::Sugar,
+            1 => ResourceKind::Protein,
+            2 => ResourceKind::Water,
+            _ => return Err(invalid("unknown resource kind")),
+        };
+        Ok(Resource {
+            resources_remaining: self.read_u32()?,
+            capacity: self.read_u32()?,
+            kind,
+        })
+    }
+
+    fn read_terrain(&mut self) -> io::Result<Terrain> {
+        match self.read_u8()? {
+            0 => Ok(Terrain::Open),
+            1 => Ok(Terrain::Wall),
+            2 => Ok(Terrain::Slow),
+            _ => Err(invalid("unknown terrain")),
+        }
+    }
+
+    fn read_pheromone_type(&mut self) -> io::Result<PheromoneType> {
+        match self.read_u8()? {
+            0 => Ok(PheromoneType::Exploration),
+            1 => Ok(PheromoneType::Resource),
+            _ => Err(invalid("unknown pheromone type")),
+        }
+    }
+
+    fn read_pheromone(&mut self) -> io::Result<Pheromone> {
+        let pheromone_type = self.read_pheromone_type()?;
+        let strength = self.read_u16()?;
+        let depreciation_rate = self.read_u16()?;
+        Ok(Pheromone {
+            strength,
+            depreciation_rate,
+            pheromone_type,
+        })
+    }
+
+    /// Reads a move written by [`write_move`], and returns the new position
+    fn read_move(&mut self, from: Coordinates) -> io::Result<Coordinates> {
+        match self.read_u8()? {
+            JUMP => self.read_coordinates(),
+            step if step < 9 => {
+                let x_amount = (step / 3) as i32 - 1;
+                let y_amount = (step % 3) as i32 - 1;
+                from.modify(x_amount, y_amount)
+                    .ok_or_else(|| invalid("ant moved outside the world"))
+            }
+            _ => Err(invalid("unknown ant move")),
+        }
+    }
+}
+
+/// An error for a replay that is not in the expected format
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
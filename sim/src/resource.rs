@@ -1,18 +1,146 @@
-use crate::ant_settings::DEFAULT_RESOURCE_SIZE;
+use crate::ant_settings::{
+    DEFAULT_RESOURCE_SIZE, PROTEIN_NUTRITIONAL_VALUE, PROTEIN_PHEROMONE_MULTIPLIER,
+    PROTEIN_PICKUP_TIME, PROTEIN_RESOURCE_SIZE, SUGAR_NUTRITIONAL_VALUE,
+    SUGAR_PHEROMONE_MULTIPLIER, SUGAR_PICKUP_TIME, SUGAR_RESOURCE_SIZE, WATER_NUTRITIONAL_VALUE,
+    WATER_PHEROMONE_MULTIPLIER, WATER_PICKUP_TIME, WATER_RESOURCE_SIZE,
+};
+use enum_map::Enum;
+use std::fmt;
+use std::fmt::{Debug, Display, Formatter};
+
+/// Every resource kind
+pub const RESOURCE_KINDS: [ResourceKind; 3] = [
+    ResourceKind::Sugar,
+    ResourceKind::Protein,
+    ResourceKind::Water,
+];
+
+/// The possible kinds of resource, that are handled differently by ants and colonies
+/// * Sugar - The default kind, picked up straight away
+/// * Protein - Worth more to the colony, but slower to pick up, and marked with stronger trails
+/// * Water - Found in larger amounts, but marked with weaker trails
+#[derive(Copy, Clone, Default, Eq, PartialEq, Enum)]
+pub enum ResourceKind {
+    #[default]
+    Sugar,
+    Protein,
+    Water,
+}
+
+impl ResourceKind {
+    /// Retrieves the amount added to a colony's stock, for each unit delivered, from [`ant_settings`]
+    pub fn get_nutritional_value(&self) -> u16 {
+        match self {
+            ResourceKind::Sugar => SUGAR_NUTRITIONAL_VALUE,
+            ResourceKind::Protein => PROTEIN_NUTRITIONAL_VALUE,
+            ResourceKind::Water => WATER_NUTRITIONAL_VALUE,
+        }
+    }
+
+    /// Retrieves the amount of units in a new resource of this kind, from [`ant_settings`]
+    pub fn get_default_size(&self) -> u32 {
+        match self {
+            ResourceKind::Sugar => SUGAR_RESOURCE_SIZE,
+            ResourceKind::Protein => PROTEIN_RESOURCE_SIZE,
+            ResourceKind::Water => WATER_RESOURCE_SIZE,
+        }
+    }
+
+    /// Retrieves the number of ticks an ant waits on the resource before picking up a unit, from [`ant_settings`]
+    pub fn get_pickup_time(&self) -> u16 {
+        match self {
+            ResourceKind::Sugar => SUGAR_PICKUP_TIME,
+            ResourceKind::Protein => PROTEIN_PICKUP_TIME,
+            ResourceKind::Water => WATER_PICKUP_TIME,
+        }
+    }
+
+    /// Retrieves how much stronger the trail left by an ant carrying this kind is, from [`ant_settings`]
+    pub fn get_pheromone_multiplier(&self) -> f64 {
+        match self {
+            ResourceKind::Sugar => SUGAR_PHEROMONE_MULTIPLIER,
+            ResourceKind::Protein => PROTEIN_PHEROMONE_MULTIPLIER,
+            ResourceKind::Water => WATER_PHEROMONE_MULTIPLIER,
+        }
+    }
+}
+
+impl Debug for ResourceKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+impl Display for ResourceKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceKind::Sugar => write!(f, "Sugar"),
+            ResourceKind::Protein => write!(f, "Protein"),
+            ResourceKind::Water => write!(f, "Water"),
+        }
+    }
+}
 
 /// A tile that ants will target, as it contains a "useful" resource
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Resource {
-    resources_remaining: u8,
+    pub(crate) resources_remaining: u32,
+    /// The amount the resource holds when full
+    pub(crate) capacity: u32,
+    pub(crate) kind: ResourceKind,
 }
 impl Default for Resource {
     fn default() -> Self {
         Resource {
             resources_remaining: DEFAULT_RESOURCE_SIZE,
+            capacity: DEFAULT_RESOURCE_SIZE,
+            kind: ResourceKind::default(),
         }
     }
 }
 impl Resource {
+    /// Creates a full sugar resource with the given amount remaining
+    pub fn new(resources_remaining: u32) -> Resource {
+        Resource::new_of_kind(ResourceKind::Sugar, resources_remaining)
+    }
+    /// Creates a full resource of the given kind, with the given amount remaining
+    pub fn new_of_kind(kind: ResourceKind, resources_remaining: u32) -> Resource {
+        Resource::new_with_capacity(kind, resources_remaining, resources_remaining)
+    }
+    /// Creates a resource of the given kind, with the given amount remaining out of the given capacity
+    ///
+    /// The capacity is at least one, so an empty resource is not counted as full
+    /// # Example
+    /// ```
+    /// use sim::resource::{Resource, ResourceKind};
+    ///
+    /// let resource = Resource::new_with_capacity(ResourceKind::Sugar, 250, 1000);
+    /// assert_eq!(resource.get_capacity(), 1000);
+    /// assert_eq!(resource.get_percentage_remaining(), 0.25);
+    /// ```
+    pub fn new_with_capacity(
+        kind: ResourceKind,
+        resources_remaining: u32,
+        capacity: u32,
+    ) -> Resource {
+        Resource {
+            resources_remaining,
+            capacity: capacity.max(1),
+            kind,
+        }
+    }
+    /// Creates a resource of the given kind, with the default size for that kind
+    /// # Example
+    /// ```
+    /// use sim::resource::{Resource, ResourceKind};
+    ///
+    /// let resource = Resource::from_kind(ResourceKind::Protein);
+    /// assert_eq!(resource.get_kind(), ResourceKind::Protein);
+    /// assert_eq!(resource.get_resources_remaining(), ResourceKind::Protein.get_default_size());
+    /// ```
+    pub fn from_kind(kind: ResourceKind) -> Resource {
+        Resource::new_of_kind(kind, kind.get_default_size())
+    }
     /// Used for when an ant "consumes" part of a resource
     ///
     /// Will reduce the resources remaining by one,
@@ -29,7 +157,7 @@ impl Resource {
     ///     println!("Amount: {}", amount);            
     /// }
     /// ```
-    pub fn consume(&mut self) -> Option<u8> {
+    pub fn consume(&mut self) -> Option<u32> {
         if let Some(resources) = self.resources_remaining.checked_sub(1) {
             self.resources_remaining = resources;
             Some(self.resources_remaining)
@@ -37,8 +165,62 @@ impl Resource {
             None
         }
     }
-    /// Returns the percentage amount of resource left, from the default starting amount
+    /// Returns the amount of resources left
+    pub fn get_resources_remaining(&self) -> u32 {
+        self.resources_remaining
+    }
+    /// Adds the given amount to the resource, without going above its capacity
+    /// # Example
+    /// ```
+    /// use sim::resource::{Resource, ResourceKind};
+    ///
+    /// let mut resource = Resource::new_with_capacity(ResourceKind::Sugar, 5, 7);
+    /// resource.regrow(3);
+    /// assert_eq!(resource.get_resources_remaining(), 7);
+    /// ```
+    pub fn regrow(&mut self, amount: u32) {
+        self.resources_remaining = self
+            .resources_remaining
+            .saturating_add(amount)
+            .min(self.capacity)
+            .max(self.resources_remaining);
+    }
+    /// Returns the kind of resource
+    pub fn get_kind(&self) -> ResourceKind {
+        self.kind
+    }
+    /// Returns the amount the resource holds when full
+    pub fn get_capacity(&self) -> u32 {
+        self.capacity
+    }
+    /// Returns the percentage amount of resource left, from the resource's own capacity
+    ///
+    /// Above one only for a resource created with more than its capacity, as regrowing stops at the capacity
     pub fn get_percentage_remaining(&self) -> f64 {
-        self.resources_remaining as f64 / DEFAULT_RESOURCE_SIZE as f64
+        self.resources_remaining as f64 / self.capacity as f64
     }
 }
+
+/// What happens to resources over time, beyond being consumed by ants
+///
+/// * Finite - Resources are removed once depleted, and never replaced
+/// * Regrow - Every `interval` ticks, each resource gains `amount`, up to its own capacity. Depleted resources regrow
+///   from nothing in the same place, with the same capacity, unless something else has been placed there
+/// * Respawn - Each depleted resource is replaced by a new default resource at a random location, `delay` ticks later
+/// * Seasonal - Every `period` ticks, `count` new default resources are spawned at random locations
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ResourcePolicy {
+    #[default]
+    Finite,
+    Regrow {
+        interval: u32,
+        amount: u32,
+    },
+    Respawn {
+        delay: u32,
+    },
+    Seasonal {
+        period: u32,
+        count: u8,
+    },
+}
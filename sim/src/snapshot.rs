@@ -0,0 +1,153 @@
+use crate::ant::AntType;
+use crate::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use crate::ant_store::AntId;
+use crate::pheromone::{Pheromone, PheromoneType};
+use crate::resource::Resource;
+use crate::terrain::Terrain;
+use crate::world::World;
+use crate::Coordinates;
+
+/// The parts of an ant needed to draw it
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AntSnapshot {
+    pub id: AntId,
+    pub ant_type: AntType,
+    pub position: Coordinates,
+}
+
+/// The position of a colony, and every ant belonging to it
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColonySnapshot {
+    pub position: Coordinates,
+    /// Every ant in the colony, sorted by identifier
+    pub ants: Vec<AntSnapshot>,
+}
+
+/// A copy of everything that can be seen in the world at a single tick
+///
+/// Snapshots can be taken from a live [`World`], or rebuilt from a [`Replay`](crate::replay::Replay), so anything that
+/// only needs to display the world can draw from either
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WorldSnapshot {
+    pub(crate) tick: u64,
+    pub(crate) colonies: Vec<ColonySnapshot>,
+    /// Every resource, sorted by position
+    pub(crate) resources: Vec<(Coordinates, Resource)>,
+    /// Every pheromone, sorted by position and then type
+    pub(crate) pheromones: Vec<(Coordinates, Pheromone)>,
+    /// Every tile that is not open ground, sorted by position
+    pub(crate) terrain: Vec<(Coordinates, Terrain)>,
+}
+
+impl WorldSnapshot {
+    /// Takes a snapshot of the given world
+    /// # Example
+    /// ```
+    /// # use sim::snapshot::WorldSnapshot;
+    /// # use sim::world::World;
+    /// let mut world = World::new_with_seed(7);
+    /// world.update();
+    ///
+    /// let snapshot = WorldSnapshot::new(&world);
+    /// assert_eq!(snapshot.get_tick(), 1);
+    /// assert_eq!(snapshot.iter_ants().count(), world.iter_ants().count());
+    /// ```
+    pub fn new(world: &World) -> WorldSnapshot {
+        let colonies = world
+            .get_colonies()
+            .iter()
+            .map(|colony| ColonySnapshot {
+                position: colony.get_position(),
+                ants: colony
+                    .iter_ants()
+                    .map(|(id, ant)| AntSnapshot {
+                        id,
+                        ant_type: ant.get_ant_type(),
+                        position: ant.get_position(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        let mut resources = Vec::new();
+        let mut pheromones = Vec::new();
+        let mut terrain = Vec::new();
+        for x in 0..WORLD_WIDTH {
+            for y in 0..WORLD_HEIGHT {
+                let coords = Coordinates::new(x, y).unwrap();
+                if world.get_terrain(coords) != Terrain::Open {
+                    terrain.push((coords, world.get_terrain(coords)));
+                }
+                if let Some(resource) = world.resources[x as usize][y as usize] {
+                    resources.push((coords, resource));
+                }
+                for (_, pheromone) in world.pheromones.get_tile(coords) {
+                    if let Some(pheromone) = pheromone {
+                        pheromones.push((coords, *pheromone));
+                    }
+                }
+            }
+        }
+        WorldSnapshot {
+            tick: world.get_tick(),
+            colonies,
+            resources,
+            pheromones,
+            terrain,
+        }
+    }
+
+    /// The number of time steps the world had executed when the snapshot was taken
+    pub fn get_tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Returns every colony, and its ants
+    pub fn get_colonies(&self) -> &[ColonySnapshot] {
+        &self.colonies
+    }
+
+    /// Returns an iterator over every ant, with the index of the colony it belongs to
+    pub fn iter_ants(&self) -> impl Iterator<Item = (usize, &AntSnapshot)> + '_ {
+        self.colonies
+            .iter()
+            .enumerate()
+            .flat_map(|(index, colony)| colony.ants.iter().map(move |ant| (index, ant)))
+    }
+
+    /// Returns an iterator over every resource, with its position
+    pub fn iter_resources(&self) -> impl Iterator<Item = (Coordinates, &Resource)> + '_ {
+        self.resources
+            .iter()
+            .map(|(coords, resource)| (*coords, resource))
+    }
+
+    /// Returns an iterator over every tile that is not open ground, with its position
+    pub fn iter_terrain(&self) -> impl Iterator<Item = (Coordinates, Terrain)> + '_ {
+        self.terrain.iter().copied()
+    }
+
+    /// Returns an iterator over every pheromone, with its position
+    pub fn iter_pheromones(&self) -> impl Iterator<Item = (Coordinates, &Pheromone)> + '_ {
+        self.pheromones
+            .iter()
+            .map(|(coords, pheromone)| (*coords, pheromone))
+    }
+}
+
+/// The key resources and terrain are sorted by in a snapshot
+pub(crate) fn position_key(coords: Coordinates) -> (u16, u16) {
+    (coords.get_x_position_u16(), coords.get_y_position_u16())
+}
+
+/// The key pheromones are sorted by in a snapshot
+pub(crate) fn pheromone_key(coords: Coordinates, pheromone_type: PheromoneType) -> (u16, u16, u8) {
+    let pheromone_type = match pheromone_type {
+        PheromoneType::Exploration => 0,
+        PheromoneType::Resource => 1,
+    };
+    (
+        coords.get_x_position_u16(),
+        coords.get_y_position_u16(),
+        pheromone_type,
+    )
+}
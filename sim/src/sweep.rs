@@ -0,0 +1,266 @@
+//! Running the simulation over a grid of parameter values, to compare how well each combination forages
+use crate::behaviour::{AntParameters, DefaultBehaviour};
+use crate::event::Event;
+use crate::world::World;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::io;
+use std::io::Write;
+#[cfg(not(feature = "parallel"))]
+use std::thread;
+
+/// The values a single parameter takes in a sweep
+#[derive(Clone, Debug, PartialEq)]
+pub struct SweepAxis {
+    /// The name of the parameter, one of [`PARAMETER_NAMES`](crate::behaviour::PARAMETER_NAMES)
+    pub name: String,
+    pub values: Vec<f64>,
+}
+
+impl SweepAxis {
+    /// Parses an axis from `NAME=start..end:step` (with both ends included) or `NAME=first,second,...`
+    ///
+    /// Returns None if the text isn't in either form, the name isn't a parameter, or the range is empty
+    /// # Example
+    /// ```
+    /// # use sim::sweep::SweepAxis;
+    /// let range = SweepAxis::parse("ANT_BACKWARDS_CHANCE=0.0..0.5:0.05").unwrap();
+    /// assert_eq!(range.values.len(), 11);
+    /// assert_eq!(range.values[10], 0.5);
+    ///
+    /// let list = SweepAxis::parse("WORKER_PHEROMONE_CHANCE=0.7,0.9").unwrap();
+    /// assert_eq!(list.values, vec![0.7, 0.9]);
+    ///
+    /// assert!(SweepAxis::parse("NOT_A_PARAMETER=1").is_none());
+    /// ```
+    pub fn parse(text: &str) -> Option<SweepAxis> {
+        let (name, values) = text.split_once('=')?;
+        AntParameters::default().get(name)?;
+        let values = match values.split_once("..") {
+            Some((start, rest)) => {
+                let (end, step) = rest.split_once(':')?;
+                let (start, end, step): (f64, f64, f64) =
+                    (start.parse().ok()?, end.parse().ok()?, step.parse().ok()?);
+                if step <= 0.0 || end < start {
+                    return None;
+                }
+                // Each value is computed from the start and rounded, so rounding errors don't build up, and the end
+                // is kept despite them
+                let count = ((end - start) / step + 1e-9).floor() as usize + 1;
+                (0..count)
+                    .map(|index| ((start + step * index as f64) * 1e9).round() / 1e9)
+                    .collect()
+            }
+            None => values
+                .split(',')
+                .map(|value| value.trim().parse().ok())
+                .collect::<Option<Vec<f64>>>()?,
+        };
+        Some(SweepAxis {
+            name: name.to_string(),
+            values,
+        })
+    }
+}
+
+/// The aggregated results of every run at a single point in a sweep
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SweepResult {
+    pub parameters: AntParameters,
+    /// The number of runs, one per seed
+    pub runs: u64,
+    /// The mean amount of food delivered in a run
+    pub mean_delivered: f64,
+    /// The standard deviation of the food delivered in a run
+    pub deviation_delivered: f64,
+    /// The mean food delivered per ant, per tick, which is the foraging efficiency
+    pub mean_efficiency: f64,
+}
+
+/// A grid of parameter values, each run for the same number of ticks over the same seeds
+///
+/// Every run starts from `World::new_with_seed`, with every colony using the default behaviour with the point's
+/// parameters. Sharing seeds between points means differences come from the parameters rather than the layout
+pub struct Sweep {
+    pub axes: Vec<SweepAxis>,
+    /// The seed of the first run at each point, with the rest following on (wrapping around after `u64::MAX`)
+    pub first_seed: u64,
+    /// The number of runs at each point
+    pub seeds: u64,
+    pub ticks: u64,
+}
+
+impl Sweep {
+    /// Returns the parameters at every point in the sweep, with the last axis changing fastest
+    pub fn get_points(&self) -> Vec<AntParameters> {
+        self.axes
+            .iter()
+            .fold(vec![AntParameters::default()], |points, axis| {
+                points
+                    .iter()
+                    .flat_map(|point| {
+                        axis.values.iter().map(move |value| {
+                            let mut point = *point;
+                            point.set(&axis.name, *value);
+                            point
+                        })
+                    })
+                    .collect()
+            })
+    }
+
+    /// Runs every point in the sweep, returning the results in the same order as [`Sweep::get_points`]
+    ///
+    /// Every run is simulated in parallel, on rayon's thread pool with the `parallel` feature, or a thread per core
+    /// without it
+    /// # Example
+    /// ```
+    /// # use sim::sweep::{Sweep, SweepAxis};
+    /// let sweep = Sweep {
+    ///     axes: vec![SweepAxis::parse("WORKER_PHEROMONE_CHANCE=0.5,0.9").unwrap()],
+    ///     first_seed: 1,
+    ///     seeds: 2,
+    ///     ticks: 20,
+    /// };
+    /// let results = sweep.run();
+    /// assert_eq!(results.len(), 2);
+    /// assert_eq!(results[1].parameters.get("WORKER_PHEROMONE_CHANCE"), Some(0.9));
+    /// assert_eq!(results, sweep.run());
+    ///
+    /// // Seeds wrap around, rather than overflowing
+    /// let wrapping = Sweep { first_seed: u64::MAX, ..sweep };
+    /// assert_eq!(wrapping.run()[0].runs, 2);
+    /// ```
+    pub fn run(&self) -> Vec<SweepResult> {
+        let runs: Vec<(AntParameters, u64)> = self
+            .get_points()
+            .into_iter()
+            .flat_map(|parameters| {
+                get_seeds(self.first_seed, self.seeds).map(move |seed| (parameters, seed))
+            })
+            .collect();
+        let outcomes = map_in_parallel(&runs, |(parameters, seed)| {
+            run_once(*parameters, *seed, self.ticks)
+        });
+        runs.chunks(self.seeds.max(1) as usize)
+            .zip(outcomes.chunks(self.seeds.max(1) as usize))
+            .map(|(runs, outcomes)| {
+                let count = outcomes.len() as f64;
+                let mean = outcomes
+                    .iter()
+                    .map(|(delivered, _)| *delivered as f64)
+                    .sum::<f64>()
+                    / count;
+                let variance = outcomes
+                    .iter()
+                    .map(|(delivered, _)| (*delivered as f64 - mean).powi(2))
+                    .sum::<f64>()
+                    / count;
+                SweepResult {
+                    parameters: runs[0].0,
+                    runs: outcomes.len() as u64,
+                    mean_delivered: mean,
+                    deviation_delivered: variance.sqrt(),
+                    mean_efficiency: outcomes
+                        .iter()
+                        .map(|(_, efficiency)| efficiency)
+                        .sum::<f64>()
+                        / count,
+                }
+            })
+            .collect()
+    }
+
+    /// Writes the results as CSV, with a column for each axis followed by the aggregated measures
+    pub fn write_csv<W: Write>(&self, results: &[SweepResult], mut writer: W) -> io::Result<()> {
+        for axis in &self.axes {
+            write!(writer, "{},", axis.name)?;
+        }
+        writeln!(
+            writer,
+            "runs,mean_delivered,deviation_delivered,mean_efficiency"
+        )?;
+        for result in results {
+            for axis in &self.axes {
+                write!(
+                    writer,
+                    "{},",
+                    result.parameters.get(&axis.name).unwrap_or(0.0)
+                )?;
+            }
+            writeln!(
+                writer,
+                "{},{},{},{}",
+                result.runs,
+                result.mean_delivered,
+                result.deviation_delivered,
+                result.mean_efficiency
+            )?;
+        }
+        writer.flush()
+    }
+}
+
+/// Simulates a single world, returning the food delivered and the food delivered per ant per tick
+pub(crate) fn run_once(parameters: AntParameters, seed: u64, ticks: u64) -> (u64, f64) {
+    let mut world = World::new_with_seed(seed);
+    for index in 0..world.get_colonies().len() {
+        if let Some(colony) = world.get_colony_mut(index) {
+            colony.set_behaviour(Box::new(DefaultBehaviour::new(parameters)));
+        }
+    }
+    let mut delivered = 0;
+    let mut ant_ticks = 0;
+    for _ in 0..ticks {
+        world.update();
+        delivered += world
+            .get_events()
+            .iter()
+            .filter(|event| matches!(event, Event::FoodDelivered { .. }))
+            .count() as u64;
+        ant_ticks += world.iter_ants().count() as u64;
+    }
+    let efficiency = if ant_ticks == 0 {
+        0.0
+    } else {
+        delivered as f64 / ant_ticks as f64
+    };
+    (delivered, efficiency)
+}
+
+/// Returns `count` seeds starting from `first`, wrapping around after `u64::MAX`
+pub(crate) fn get_seeds(first: u64, count: u64) -> impl Iterator<Item = u64> {
+    (0..count).map(move |offset| first.wrapping_add(offset))
+}
+
+/// Applies the function to every item in parallel, returning the results in the same order as the items
+#[cfg(feature = "parallel")]
+pub(crate) fn map_in_parallel<T: Sync, R: Send, F: Fn(&T) -> R + Sync>(
+    items: &[T],
+    function: F,
+) -> Vec<R> {
+    items.par_iter().map(&function).collect()
+}
+
+/// Applies the function to every item in parallel, returning the results in the same order as the items
+///
+/// The items are split into a contiguous chunk for each core, each mapped by its own scoped thread
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map_in_parallel<T: Sync, R: Send, F: Fn(&T) -> R + Sync>(
+    items: &[T],
+    function: F,
+) -> Vec<R> {
+    let workers = thread::available_parallelism().map_or(1, |workers| workers.get());
+    let chunk_size = items.len().div_ceil(workers).max(1);
+    let function = &function;
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(function).collect::<Vec<R>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
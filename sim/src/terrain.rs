@@ -0,0 +1,56 @@
+use crate::ant_settings::SLOW_TERRAIN_MOVE_COST;
+use std::fmt;
+use std::fmt::{Debug, Display, Formatter};
+
+/// The ground on a single tile of the world
+///
+/// * Open - Can be walked over freely
+/// * Slow - Can be walked over, but ants take several ticks to leave it
+/// * Wall - Can never be entered by ants
+#[derive(Copy, Clone, Default, Eq, PartialEq)]
+pub enum Terrain {
+    #[default]
+    Open,
+    Slow,
+    Wall,
+}
+
+impl Terrain {
+    /// Returns true if ants can stand on this terrain
+    pub fn is_passable(&self) -> bool {
+        match self {
+            Terrain::Open | Terrain::Slow => true,
+            Terrain::Wall => false,
+        }
+    }
+
+    /// Returns the number of ticks an ant spends on this terrain before it can move off it, from [`ant_settings`]
+    /// # Example
+    /// ```
+    /// # use sim::terrain::Terrain;
+    /// assert_eq!(Terrain::Open.get_move_cost(), 1);
+    /// assert!(Terrain::Slow.get_move_cost() > 1);
+    /// ```
+    pub fn get_move_cost(&self) -> u16 {
+        match self {
+            Terrain::Open | Terrain::Wall => 1,
+            Terrain::Slow => SLOW_TERRAIN_MOVE_COST,
+        }
+    }
+}
+
+impl Debug for Terrain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+impl Display for Terrain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Terrain::Open => write!(f, "Open"),
+            Terrain::Slow => write!(f, "Slow"),
+            Terrain::Wall => write!(f, "Wall"),
+        }
+    }
+}
@@ -1,12 +1,51 @@
 extern crate enum_map;
 
-use crate::ant::AntType;
+use crate::ant::{Ant, AntType, AntUpdate, ANT_TYPES};
 use crate::ant_settings::{DEFAULT_RESOURCE_COUNT, WORLD_HEIGHT, WORLD_WIDTH};
+use crate::ant_store::AntId;
 use crate::colony::Colony;
+use crate::distribution::ResourceDistribution;
+use crate::event::{Event, Observer};
 use crate::pheromone::{Pheromone, PheromoneType};
-use crate::resource::Resource;
-use crate::Coordinates;
+use crate::pheromone_field::PheromoneField;
+use crate::resource::{Resource, ResourceKind, ResourcePolicy};
+use crate::terrain::Terrain;
+use crate::{mix_seed, Coordinates};
 use enum_map::EnumMap;
+use rand::SeedableRng;
+use rand_pcg::Pcg64Mcg;
+
+/// A grid containing every resource in the world
+pub type ResourceMap = [[Option<Resource>; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
+/// A grid containing the number of ants on each tile
+pub type AntCountMap = [[u32; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
+/// A grid containing the terrain of every tile
+pub type TerrainMap = [[Terrain; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
+
+/// A copy of an ant, with the colony it belongs to
+#[derive(Copy, Clone)]
+pub struct AntInfo {
+    /// The index of the colony the ant belongs to
+    pub colony: usize,
+    /// The identifier of the ant, unique within its colony
+    pub id: AntId,
+    /// A copy of the ant
+    pub ant: Ant,
+}
+
+/// Everything on a single tile of the world
+pub struct Tile<'a> {
+    /// The position of the tile
+    pub coords: Coordinates,
+    /// The resource on the tile, if there is one
+    pub resource: Option<&'a Resource>,
+    /// The pheromones on the tile, by type
+    pub pheromones: &'a EnumMap<PheromoneType, Option<Pheromone>>,
+    /// Every ant on the tile
+    pub ants: Vec<AntInfo>,
+    /// The index of the colony on the tile, if there is one
+    pub colony: Option<usize>,
+}
 
 /// A struct containing every entity in the world
 ///
@@ -14,16 +53,29 @@ use enum_map::EnumMap;
 pub struct World {
     // TODO Find a more efficient memory solution, that is just as fast (without the cost of btmaps or hashmaps)
     /// A container all active resources
-    pub resources: [[Option<Resource>; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize],
+    pub(crate) resources: ResourceMap,
     /// Contains the coordinates for all active resource objects, for fast iteration
-    pub resource_lookup: Vec<Coordinates>,
+    pub(crate) resource_lookup: Vec<Coordinates>,
     /// A container for all active colonies
-    pub colonies: Vec<Colony>,
+    pub(crate) colonies: Vec<Colony>,
     /// A container for all active pheromones
-    pub pheromones:
-    [[EnumMap<PheromoneType, Option<Pheromone>>; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize],
-    /// Contains the coordinates for all active pheromones, for fast iteration
-    pub pheromone_lookup: Vec<(Coordinates, PheromoneType)>,
+    pub(crate) pheromones: PheromoneField,
+    /// The terrain of every tile
+    pub(crate) terrain: TerrainMap,
+    /// The seed that all randomness in the world is derived from
+    seed: u64,
+    /// The number of time steps that have been executed
+    tick: u64,
+    /// The random number generator used for spawning entities
+    rng: Pcg64Mcg,
+    /// Every registered observer, notified of each event as it happens
+    observers: Vec<Box<dyn Observer>>,
+    /// The events emitted during the last time step
+    events: Vec<Event>,
+    /// What happens to resources over time
+    resource_policy: ResourcePolicy,
+    /// Where resources were depleted, what they were, and on which tick, while the policy still needs to replace them
+    depleted: Vec<(Coordinates, Resource, u64)>,
 }
 
 impl Default for World {
@@ -33,14 +85,34 @@ impl Default for World {
 }
 
 impl World {
+    /// Creates a new World with a default colony and randomly placed resources, using a random seed
     pub fn new() -> World {
-        let mut world = World {
-            resources: [[None; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize],
-            resource_lookup: Vec::new(),
-            colonies: Vec::new(),
-            pheromones: [[EnumMap::new(); WORLD_HEIGHT as usize]; WORLD_WIDTH as usize],
-            pheromone_lookup: Vec::new(),
-        };
+        World::new_with_seed(rand::random())
+    }
+
+    /// Creates a new World with a default colony and randomly placed resources
+    ///
+    /// Worlds created with the same seed will always evolve identically
+    /// # Example
+    /// ```
+    /// # use sim::world::World;
+    /// let mut first = World::new_with_seed(42);
+    /// let mut second = World::new_with_seed(42);
+    /// for _ in 0..50 {
+    ///     first.update();
+    ///     second.update();
+    /// }
+    /// let strengths = |world: &World| {
+    ///     world
+    ///         .iter_pheromones()
+    ///         .map(|(coords, pheromone)| (coords, pheromone.get_strength()))
+    ///         .collect::<Vec<_>>()
+    /// };
+    /// assert_eq!(strengths(&first), strengths(&second));
+    /// ```
+    pub fn new_with_seed(seed: u64) -> World {
+        let mut world = World::new_with_data(Vec::new(), Vec::new());
+        world.set_seed(seed);
         world.new_colony();
         for _ in 0..DEFAULT_RESOURCE_COUNT {
             world.new_resource();
@@ -54,38 +126,308 @@ impl World {
     /// * `food*` A vector with all food instances that should exist on creation
     /// * `colonies*` A vector with all colonies instances that should exist on creation
     ///
+    /// A random seed is used, which can be replaced with [`World::set_seed`]
     pub fn new_with_data(food: Vec<(Coordinates, Resource)>, colonies: Vec<Colony>) -> World {
+        let seed = rand::random();
         let mut food_container = [[None; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
         let mut food_lookup = Vec::new();
         for (coords, food_entry) in food {
-            food_container[coords.x_position as usize][coords.y_position as usize] =
-                Some(food_entry);
-            food_lookup.push(coords);
+            let tile = &mut food_container[coords.x_position as usize][coords.y_position as usize];
+            if tile.is_none() {
+                food_lookup.push(coords);
+            }
+            *tile = Some(food_entry);
         }
         World {
             resources: food_container,
             resource_lookup: food_lookup,
             colonies,
-            pheromones: [[EnumMap::new(); WORLD_HEIGHT as usize]; WORLD_WIDTH as usize],
-            pheromone_lookup: Vec::new(),
+            pheromones: PheromoneField::new(),
+            terrain: [[Terrain::Open; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize],
+            seed,
+            tick: 0,
+            rng: Pcg64Mcg::seed_from_u64(seed),
+            observers: Vec::new(),
+            events: Vec::new(),
+            resource_policy: ResourcePolicy::default(),
+            depleted: Vec::new(),
+        }
+    }
+
+    /// Replaces the seed that all randomness in the world is derived from
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = Pcg64Mcg::seed_from_u64(seed);
+    }
+
+    /// The seed that all randomness in the world is derived from
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The number of time steps that have been executed
+    pub fn get_tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Returns every colony in the world
+    pub fn get_colonies(&self) -> &[Colony] {
+        &self.colonies
+    }
+
+    /// Returns an iterator over a copy of every ant in the world, with the colony it belongs to
+    pub fn iter_ants(&self) -> impl Iterator<Item = AntInfo> + '_ {
+        self.colonies
+            .iter()
+            .enumerate()
+            .flat_map(|(colony, colony_data)| {
+                colony_data
+                    .iter_ants()
+                    .map(move |(id, ant)| AntInfo { colony, id, ant })
+            })
+    }
+
+    /// Returns an iterator over every active pheromone, with its position
+    pub fn iter_pheromones(&self) -> impl Iterator<Item = (Coordinates, &Pheromone)> + '_ {
+        self.pheromones.iter()
+    }
+
+    /// Returns an iterator over every resource, with its position
+    pub fn iter_resources(&self) -> impl Iterator<Item = (Coordinates, &Resource)> + '_ {
+        self.resource_lookup.iter().filter_map(move |coords| {
+            self.resources[coords.get_x_position_usize()][coords.get_y_position_usize()]
+                .as_ref()
+                .map(|resource| (*coords, resource))
+        })
+    }
+
+    /// Returns everything on the given tile
+    /// # Example
+    /// ```
+    /// # use sim::colony::Colony;
+    /// # use sim::resource::Resource;
+    /// # use sim::world::World;
+    /// # use sim::Coordinates;
+    /// let food = Coordinates::new(2, 3).unwrap();
+    /// let world = World::new_with_data(vec![(food, Resource::default())], vec![Colony::default()]);
+    ///
+    /// let tile = world.get_tile(food);
+    /// assert!(tile.resource.is_some());
+    /// assert!(tile.colony.is_none());
+    /// assert!(tile.ants.is_empty());
+    /// ```
+    pub fn get_tile(&self, coords: Coordinates) -> Tile<'_> {
+        Tile {
+            coords,
+            resource: self.resources[coords.get_x_position_usize()][coords.get_y_position_usize()]
+                .as_ref(),
+            pheromones: self.pheromones.get_tile(coords),
+            ants: self
+                .iter_ants()
+                .filter(|info| info.ant.get_position() == coords)
+                .collect(),
+            colony: self
+                .colonies
+                .iter()
+                .position(|colony| colony.get_position() == coords),
         }
     }
+
+    /// Places the given pheromone at the given position, replacing any pheromone of the same type
+    pub fn add_pheromone(&mut self, coords: Coordinates, pheromone: Pheromone) {
+        self.pheromones.insert(coords, pheromone);
+    }
     /// Creates a new default colony, and adds it to the world
     pub fn new_colony(&mut self) {
         self.colonies.push(Colony::default());
     }
     /// Spawns a new resource at a random location
     ///
-    /// Providing it is not occupied by another resource
+    /// Providing it is not occupied by another resource, a colony, or a wall
+    ///
+    /// Does nothing if every open tile already has a resource or a colony
     pub fn new_resource(&mut self) {
-        let mut coords = Coordinates::new_random();
-        while self.resources[coords.get_x_position_usize()][coords.get_y_position_usize()].is_some()
-        {
-            coords = Coordinates::new_random();
+        self.spawn_random_resource(Resource::default());
+    }
+
+    /// Places the given resource on a random open tile without a resource or a colony, returning where it was placed
+    fn spawn_random_resource(&mut self, resource: Resource) -> Option<Coordinates> {
+        let is_free = |world: &World, coords: Coordinates| {
+            world.resources[coords.get_x_position_usize()][coords.get_y_position_usize()].is_none()
+                && world.get_terrain(coords).is_passable()
+                && world
+                    .colonies
+                    .iter()
+                    .all(|colony| colony.get_position() != coords)
+        };
+        let any_free = (0..WORLD_WIDTH)
+            .flat_map(|x| (0..WORLD_HEIGHT).map(move |y| Coordinates::new(x, y).unwrap()))
+            .any(|coords| is_free(self, coords));
+        if !any_free {
+            return None;
+        }
+        let mut coords = Coordinates::new_random_from(&mut self.rng);
+        while !is_free(self, coords) {
+            coords = Coordinates::new_random_from(&mut self.rng);
+        }
+        self.set_resource(coords, resource);
+        Some(coords)
+    }
+
+    /// Places resources as the given distribution chooses, using the world's random number generator, and returns
+    /// where they were placed
+    ///
+    /// Generated resources are copies of `resource`, unless the distribution gives its own
+    /// # Example
+    /// ```
+    /// # use sim::distribution::ResourceDistribution;
+    /// # use sim::resource::Resource;
+    /// # use sim::world::World;
+    /// let mut world = World::new_with_seed(8);
+    /// let before = world.iter_resources().count();
+    /// let distribution = ResourceDistribution::Clustered { clusters: 2, per_cluster: 4, radius: 1 };
+    ///
+    /// let placed = world.distribute_resources(&distribution, Resource::default());
+    /// assert_eq!(placed.len(), 8);
+    /// assert_eq!(world.iter_resources().count(), before + 8);
+    ///
+    /// // Asking for more than there is room for fills every open tile, and stops
+    /// let everywhere = ResourceDistribution::Uniform { count: u8::MAX };
+    /// world.distribute_resources(&everywhere, Resource::default());
+    /// assert!(world.distribute_resources(&everywhere, Resource::default()).is_empty());
+    ///
+    /// // Which leaves the colonies clear
+    /// for colony in world.get_colonies() {
+    ///     assert!(world.get_tile(colony.get_position()).resource.is_none());
+    /// }
+    /// ```
+    pub fn distribute_resources(
+        &mut self,
+        distribution: &ResourceDistribution,
+        resource: Resource,
+    ) -> Vec<Coordinates> {
+        // The distribution reads the world while using a copy of its generator, which then replaces the original
+        let mut rng = self.rng.clone();
+        let placed = distribution.generate(self, resource, &mut rng);
+        self.rng = rng;
+        placed
+            .into_iter()
+            .map(|(coords, resource)| {
+                self.set_resource(coords, resource);
+                coords
+            })
+            .collect()
+    }
+
+    /// Places the given resource at the given position, replacing any resource already there
+    pub fn set_resource(&mut self, coords: Coordinates, resource: Resource) {
+        let tile =
+            &mut self.resources[coords.get_x_position_usize()][coords.get_y_position_usize()];
+        if tile.is_none() {
+            self.resource_lookup.push(coords);
+        }
+        *tile = Some(resource);
+    }
+
+    /// Removes the resource at the given position, returning it if it existed
+    pub fn remove_resource(&mut self, coords: Coordinates) -> Option<Resource> {
+        let resource =
+            self.resources[coords.get_x_position_usize()][coords.get_y_position_usize()].take()?;
+        self.resource_lookup.retain(|entry| *entry != coords);
+        Some(resource)
+    }
+
+    /// Replaces what happens to resources over time
+    /// # Example
+    /// ```
+    /// # use sim::resource::ResourcePolicy;
+    /// # use sim::world::World;
+    /// let mut world = World::new_with_data(Vec::new(), Vec::new());
+    /// world.set_resource_policy(ResourcePolicy::Seasonal { period: 10, count: 1 });
+    ///
+    /// for _ in 0..9 {
+    ///     world.update();
+    /// }
+    /// assert_eq!(world.iter_resources().count(), 0);
+    /// world.update();
+    /// assert_eq!(world.iter_resources().count(), 1);
+    /// ```
+    pub fn set_resource_policy(&mut self, policy: ResourcePolicy) {
+        self.resource_policy = policy;
+        self.depleted.clear();
+    }
+
+    /// Returns what happens to resources over time
+    pub fn get_resource_policy(&self) -> ResourcePolicy {
+        self.resource_policy
+    }
+
+    /// Returns the terrain of the given tile
+    pub fn get_terrain(&self, coords: Coordinates) -> Terrain {
+        self.terrain[coords.get_x_position_usize()][coords.get_y_position_usize()]
+    }
+
+    /// Replaces the terrain of the given tile
+    ///
+    /// Ants already standing on a tile that becomes a wall can still leave it
+    ///
+    /// Returns false, leaving the tile unchanged, for a wall on a colony or resource, as ants could never reach it
+    /// # Example
+    /// ```
+    /// # use sim::colony::Colony;
+    /// # use sim::resource::Resource;
+    /// # use sim::terrain::Terrain;
+    /// # use sim::world::World;
+    /// # use sim::Coordinates;
+    /// let food = Coordinates::new(2, 3).unwrap();
+    /// let nest = Coordinates::new(5, 5).unwrap();
+    /// let mut world = World::new_with_data(vec![(food, Resource::default())], vec![Colony::new(nest)]);
+    ///
+    /// assert!(!world.set_terrain(food, Terrain::Wall));
+    /// assert!(!world.set_terrain(nest, Terrain::Wall));
+    /// assert_eq!(world.get_terrain(food), Terrain::Open);
+    ///
+    /// assert!(world.set_terrain(food, Terrain::Slow));
+    /// assert!(world.set_terrain(Coordinates::new(0, 0).unwrap(), Terrain::Wall));
+    /// ```
+    pub fn set_terrain(&mut self, coords: Coordinates, terrain: Terrain) -> bool {
+        if !terrain.is_passable() {
+            let tile = self.get_tile(coords);
+            if tile.colony.is_some() || tile.resource.is_some() {
+                return false;
+            }
         }
-        self.resources[coords.get_x_position_usize()][coords.get_y_position_usize()] =
-            Some(Resource::default());
-        self.resource_lookup.push(coords);
+        self.terrain[coords.get_x_position_usize()][coords.get_y_position_usize()] = terrain;
+        true
+    }
+
+    /// Returns the colony with the given index, to be changed, if it exists
+    pub fn get_colony_mut(&mut self, index: usize) -> Option<&mut Colony> {
+        self.colonies.get_mut(index)
+    }
+
+    /// Adds the given colony to the world, and returns its index
+    pub fn add_colony(&mut self, colony: Colony) -> usize {
+        self.colonies.push(colony);
+        self.colonies.len() - 1
+    }
+
+    /// Removes the colony with the given index, along with all of its ants, and returns it if it existed
+    ///
+    /// The index of every later colony is reduced by one
+    pub fn remove_colony(&mut self, index: usize) -> Option<Colony> {
+        if index >= self.colonies.len() {
+            return None;
+        }
+        let colony = self.colonies.remove(index);
+        for (id, ant) in colony.iter_ants() {
+            self.emit(Event::AntDied {
+                colony: index,
+                id,
+                position: ant.get_position(),
+            });
+        }
+        Some(colony)
     }
 
     /// The main updater method
@@ -93,29 +435,220 @@ impl World {
     /// * Spawn any new food/ants if required
     /// * Update the position of ants
     /// * Update the strength of pheromones and remove them if necessary
+    ///
+    /// Ants decide their moves against the state of the world at the start of the time step (in parallel, with the
+    /// `parallel` feature), and then the food they pick up and the pheromones they leave are applied in a fixed order
+    ///
+    /// Every change is reported as an [`Event`], to the registered observers and through [`World::get_events`]
     pub fn update(&mut self) {
-        for colony in &mut self.colonies {
-            colony.update(
-                &mut self.resources,
-                &mut self.pheromone_lookup,
-                &mut self.pheromones,
-            );
-        }
-        let mut new_lookup = self.pheromone_lookup.clone();
-        new_lookup.retain(|(coords, pheromone_type)| {
-            let mut retain = true;
-            if let Some(pheromones) = &mut self.pheromones[coords.x_position as usize]
-                [coords.y_position as usize][*pheromone_type]
-            {
-                retain = pheromones.update();
+        self.events.clear();
+        let ant_counts = self.count_ants();
+        let mut updates = Vec::with_capacity(self.colonies.len());
+        for (index, colony) in self.colonies.iter_mut().enumerate() {
+            let seed = mix_seed(self.seed, &[self.tick, index as u64]);
+            updates.push(colony.update(
+                &self.resources,
+                &self.pheromones,
+                &ant_counts,
+                &self.terrain,
+                seed,
+            ));
+        }
+        for (colony, colony_update) in updates.into_iter().enumerate() {
+            for (id, ant_type) in colony_update.spawned {
+                self.emit(Event::AntSpawned {
+                    colony,
+                    id,
+                    ant_type,
+                });
             }
-            if !retain {
-                self.pheromones[coords.x_position as usize][coords.y_position as usize]
-                    [*pheromone_type] = None;
+            for (id, ant_update) in colony_update.ants {
+                self.apply_ant_update(colony, id, ant_update);
             }
-            retain
+        }
+        for (position, pheromone_type) in self.pheromones.decay() {
+            self.emit(Event::PheromoneExpired {
+                position,
+                pheromone_type,
+            });
+        }
+        self.apply_resource_policy();
+        self.tick += 1;
+    }
+
+    /// Applies the food picked up, and pheromone left by an ant
+    fn apply_ant_update(&mut self, colony: usize, id: AntId, ant_update: AntUpdate) {
+        // An ant only carries food if there was still some left for it, after the ants applied before it
+        if let Some(position) = ant_update.consumed {
+            if let Some((kind, depleted)) = self.consume_resource(position) {
+                self.colonies[colony].pick_up(id, kind);
+                self.emit(Event::FoodPickedUp {
+                    colony,
+                    id,
+                    position,
+                    kind,
+                });
+                if depleted {
+                    self.emit(Event::ResourceDepleted { position });
+                }
+            }
+        }
+        if let Some(kind) = ant_update.delivered {
+            self.colonies[colony].add_stock(kind, kind.get_nutritional_value() as u32);
+            self.emit(Event::FoodDelivered {
+                colony,
+                id,
+                position: self.colonies[colony].get_position(),
+                kind,
+            });
+        }
+        if let Some((position, pheromone_type, amount)) = ant_update.pheromone {
+            if self.pheromones.deposit(position, pheromone_type, amount) {
+                self.emit(Event::PheromoneCreated {
+                    position,
+                    pheromone_type,
+                });
+            }
+        }
+    }
+
+    /// Removes one unit of food from the resource at the given position, and removes the resource once it is depleted
+    ///
+    /// Several ants can pick up food from the same tile in one time step, so the resource may already be gone
+    ///
+    /// Returns None if there was no resource, otherwise its kind and whether the resource was depleted
+    fn consume_resource(&mut self, coords: Coordinates) -> Option<(ResourceKind, bool)> {
+        let tile = &mut self.resources[coords.x_position as usize][coords.y_position as usize];
+        let resource = tile.as_mut()?;
+        let kind = resource.get_kind();
+        let original = *resource;
+        match resource.consume() {
+            Some(remaining) if remaining > 0 => Some((kind, false)),
+            _ => {
+                self.remove_resource(coords);
+                if matches!(
+                    self.resource_policy,
+                    ResourcePolicy::Regrow { .. } | ResourcePolicy::Respawn { .. }
+                ) {
+                    self.depleted.push((coords, original, self.tick));
+                }
+                Some((kind, true))
+            }
+        }
+    }
+
+    /// Regrows, respawns or spawns resources, as the resource policy requires
+    fn apply_resource_policy(&mut self) {
+        match self.resource_policy {
+            ResourcePolicy::Finite => {}
+            ResourcePolicy::Regrow { interval, amount } => {
+                if !(self.tick + 1).is_multiple_of(interval.max(1) as u64) {
+                    return;
+                }
+                for coords in &self.resource_lookup {
+                    if let Some(resource) = &mut self.resources[coords.get_x_position_usize()]
+                        [coords.get_y_position_usize()]
+                    {
+                        resource.regrow(amount);
+                    }
+                }
+                for (position, resource, _) in std::mem::take(&mut self.depleted) {
+                    let free = self.resources[position.get_x_position_usize()]
+                        [position.get_y_position_usize()]
+                    .is_none()
+                        && self.get_terrain(position).is_passable();
+                    if free && amount > 0 {
+                        self.set_resource(
+                            position,
+                            Resource::new_with_capacity(
+                                resource.get_kind(),
+                                amount.min(resource.get_capacity()),
+                                resource.get_capacity(),
+                            ),
+                        );
+                        self.emit(Event::ResourceSpawned { position });
+                    }
+                }
+            }
+            ResourcePolicy::Respawn { delay } => {
+                let tick = self.tick;
+                let (due, waiting): (Vec<_>, Vec<_>) = std::mem::take(&mut self.depleted)
+                    .into_iter()
+                    .partition(|(_, _, depleted_at)| tick >= depleted_at + delay as u64);
+                self.depleted = waiting;
+                // Resources that can't be placed yet, as every open tile is taken, wait for the next tick
+                for entry in due {
+                    match self.spawn_random_resource(Resource::new_of_kind(
+                        entry.1.get_kind(),
+                        entry.1.get_capacity(),
+                    )) {
+                        Some(position) => self.emit(Event::ResourceSpawned { position }),
+                        None => self.depleted.push(entry),
+                    }
+                }
+            }
+            ResourcePolicy::Seasonal { period, count } => {
+                if !(self.tick + 1).is_multiple_of(period.max(1) as u64) {
+                    return;
+                }
+                for _ in 0..count {
+                    if let Some(position) = self.spawn_random_resource(Resource::default()) {
+                        self.emit(Event::ResourceSpawned { position });
+                    }
+                }
+            }
+        }
+    }
+
+    /// Registers an observer, that will be notified of every event from now on
+    pub fn add_observer(&mut self, observer: Box<dyn Observer>) {
+        self.observers.push(observer);
+    }
+
+    /// Returns the events emitted during the last time step, in the order they happened
+    /// # Example
+    /// ```
+    /// # use sim::event::Event;
+    /// # use sim::world::World;
+    /// let mut world = World::new_with_seed(3);
+    /// world.update();
+    /// assert!(world
+    ///     .get_events()
+    ///     .iter()
+    ///     .any(|event| matches!(event, Event::AntSpawned { .. })));
+    /// ```
+    pub fn get_events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Removes the given ant from the world, returning it if it existed
+    pub fn remove_ant(&mut self, colony: usize, id: AntId) -> Option<Ant> {
+        let ant = self.colonies.get_mut(colony)?.remove_ant(id)?;
+        self.emit(Event::AntDied {
+            colony,
+            id,
+            position: ant.get_position(),
         });
-        self.pheromone_lookup = new_lookup;
+        Some(ant)
+    }
+
+    /// Records the event, and notifies every observer
+    fn emit(&mut self, event: Event) {
+        for observer in &mut self.observers {
+            observer.notify(self.tick, &event);
+        }
+        self.events.push(event);
+    }
+
+    /// Counts the number of ants on every tile, across all colonies
+    fn count_ants(&self) -> AntCountMap {
+        let mut ant_counts = [[0; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
+        for colony in &self.colonies {
+            for position in colony.ants.positions() {
+                ant_counts[position.x_position as usize][position.y_position as usize] += 1;
+            }
+        }
+        ant_counts
     }
 
     /// Prints a grid of the world
@@ -124,13 +657,11 @@ impl World {
         self.stats();
         let mut grid = vec![vec![' '; WORLD_WIDTH as usize]; WORLD_HEIGHT as usize];
         for colony in &self.colonies {
-            for (ant_type, ants) in &colony.ants {
-                for ant in ants {
-                    grid[ant.position.y_position as usize][ant.position.x_position as usize] =
-                        match ant_type {
-                            AntType::Scout => 'S',
-                            AntType::Worker => 'W',
-                        }
+            for (ant_type, position) in colony.ants.ant_types().iter().zip(colony.ants.positions())
+            {
+                grid[position.y_position as usize][position.x_position as usize] = match ant_type {
+                    AntType::Scout => 'S',
+                    AntType::Worker => 'W',
                 }
             }
             grid[colony.position.y_position as usize][colony.position.x_position as usize] = 'C';
@@ -151,8 +682,12 @@ impl World {
         println!("    Number of Colonies: {}", self.colonies.len());
         for (index, colony) in self.colonies.iter().enumerate() {
             println!("        Colony: {}", index);
-            for (ant_type, ants) in &colony.ants {
-                println!("        Type: {} Number {}", ant_type, ants.len());
+            for ant_type in &ANT_TYPES {
+                println!(
+                    "        Type: {} Number {}",
+                    ant_type,
+                    colony.ants.count(*ant_type)
+                );
             }
         }
     }
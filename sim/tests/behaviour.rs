@@ -0,0 +1,139 @@
+use rand::SeedableRng;
+use rand_pcg::Pcg64Mcg;
+use sim::ant::{Ant, AntType};
+use sim::ant_settings::{MAXIMUM_PHEROMONE_STRENGTH, WORLD_HEIGHT, WORLD_WIDTH};
+use sim::behaviour::{Action, AntParameters, Behaviour, DefaultBehaviour, Neighbourhood};
+use sim::pheromone::{Pheromone, PheromoneType};
+use sim::pheromone_field::PheromoneField;
+use sim::terrain::Terrain;
+use sim::world::{AntCountMap, ResourceMap, TerrainMap};
+use sim::Coordinates;
+
+/// The number of decisions sampled in each test
+const DRAWS: usize = 20_000;
+/// How far the share of decisions for a tile may be from its expected probability
+const TOLERANCE: f64 = 0.02;
+
+/// A worker between the colony and the edge of the world, so some of its moves go backwards
+fn worker() -> Ant {
+    let colony = Coordinates::new(8, 4).unwrap();
+    Ant::new(AntType::Worker, Coordinates::new(8, 8).unwrap(), colony)
+}
+
+/// Parameters where workers always follow pheromones
+fn following_parameters(alpha: f64, beta: f64, backwards_chance: f64, tau: f64) -> AntParameters {
+    let mut parameters = AntParameters::default();
+    parameters.set("WORKER_PHEROMONE_CHANCE", 1.0);
+    parameters.set("WORKER_PHEROMONE_ALPHA", alpha);
+    parameters.set("WORKER_HEURISTIC_BETA", beta);
+    parameters.set("WORKER_BACKWARDS_CHANCE", backwards_chance);
+    parameters.set("INITIAL_PHEROMONE_TAU", tau);
+    parameters
+}
+
+/// Counts how often each tile is chosen by the behaviour, over many seeded decisions
+fn sample(
+    ant: &Ant,
+    pheromones: &PheromoneField,
+    parameters: AntParameters,
+) -> Vec<(Coordinates, usize)> {
+    let resources: ResourceMap = [[None; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
+    let ant_counts: AntCountMap = [[0; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
+    let terrain: TerrainMap = [[Terrain::Open; WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
+    let neighbourhood = Neighbourhood::new(
+        ant.get_position(),
+        &resources,
+        pheromones,
+        &ant_counts,
+        &terrain,
+    );
+    let behaviour = DefaultBehaviour::new(parameters);
+    let mut rng = Pcg64Mcg::seed_from_u64(27);
+    let mut counts: Vec<(Coordinates, usize)> = neighbourhood
+        .get_adjacent_tiles()
+        .into_iter()
+        .map(|coords| (coords, 0))
+        .collect();
+    for _ in 0..DRAWS {
+        if let Action::Move(coords) = behaviour.next_action(ant, &neighbourhood, &mut rng) {
+            if let Some((_, count)) = counts.iter_mut().find(|(tile, _)| *tile == coords) {
+                *count += 1;
+            }
+        }
+    }
+    counts
+}
+
+#[test]
+fn tiles_are_chosen_in_proportion_to_their_weight() {
+    let ant = worker();
+    let (alpha, beta, backwards_chance, tau): (f64, f64, f64, f64) = (2.0, 1.0, 0.5, 0.01);
+    let strengths = [
+        ((7, 8), MAXIMUM_PHEROMONE_STRENGTH),
+        ((9, 8), MAXIMUM_PHEROMONE_STRENGTH / 2),
+        ((8, 7), MAXIMUM_PHEROMONE_STRENGTH),
+        ((8, 9), MAXIMUM_PHEROMONE_STRENGTH / 4),
+    ];
+    let mut pheromones = PheromoneField::new();
+    for ((x, y), strength) in &strengths {
+        let pheromone = Pheromone::new(*strength, 1, PheromoneType::Resource).unwrap();
+        pheromones.insert(Coordinates::new(*x, *y).unwrap(), pheromone);
+    }
+
+    let weight = |coords: Coordinates| {
+        let strength = strengths
+            .iter()
+            .find(|((x, y), _)| Coordinates::new(*x, *y) == Some(coords))
+            .map(|(_, strength)| *strength)
+            .unwrap_or(0);
+        let tau = strength as f64 / MAXIMUM_PHEROMONE_STRENGTH as f64 + tau;
+        let eta = if ant.is_correct_direction(coords) {
+            1.0
+        } else {
+            backwards_chance
+        };
+        tau.powf(alpha) * eta.powf(beta)
+    };
+    let counts = sample(
+        &ant,
+        &pheromones,
+        following_parameters(alpha, beta, backwards_chance, tau),
+    );
+    let total_weight: f64 = counts.iter().map(|(coords, _)| weight(*coords)).sum();
+    for (coords, count) in counts {
+        let expected = weight(coords) / total_weight;
+        let observed = count as f64 / DRAWS as f64;
+        assert!(
+            (observed - expected).abs() < TOLERANCE,
+            "{} chosen {} of the time, expected {}",
+            coords,
+            observed,
+            expected
+        );
+    }
+}
+
+#[test]
+fn tiles_with_no_weight_are_never_chosen() {
+    let ant = worker();
+    // Without an initial trail level, a tile without a pheromone has no weight, and without any chance of going
+    // backwards, neither does a tile back towards the colony
+    let parameters = following_parameters(1.0, 1.0, 0.0, 0.0);
+    let backwards = Coordinates::new(8, 7).unwrap();
+    let bare = Coordinates::new(9, 8).unwrap();
+    let mut pheromones = PheromoneField::new();
+    for coords in [(7, 8), (8, 7), (8, 9)].iter() {
+        let coords = Coordinates::new(coords.0, coords.1).unwrap();
+        pheromones.insert(coords, Pheromone::default(PheromoneType::Resource));
+    }
+    assert!(!ant.is_correct_direction(backwards));
+
+    let counts = sample(&ant, &pheromones, parameters);
+    for (coords, count) in counts {
+        if coords == backwards || coords == bare {
+            assert_eq!(count, 0, "{} has no weight, but was chosen", coords);
+        } else {
+            assert!(count > 0, "{} has weight, but was never chosen", coords);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! Checks a seeded world always plays out the same way, with or without the `parallel` feature
+//!
+//! Run under both `cargo test` and `cargo test --features parallel`. If the simulation or the replay format changes
+//! on purpose, the golden values below need recording again
+use sim::replay::{Recorder, Replay};
+use sim::world::World;
+
+/// The seed of the world that is recorded
+const SEED: u64 = 29;
+/// The number of ticks recorded
+const TICKS: u64 = 200;
+/// The number of frames between keyframes in the recording
+const KEYFRAME_INTERVAL: u32 = 10;
+
+/// The length of the recording, in bytes
+const GOLDEN_LENGTH: usize = 112_782;
+/// The FNV-1a hash of the recording
+const GOLDEN_HASH: u64 = 0x1e4f_2c72_6a0b_6b31;
+
+/// Hashes the given bytes with 64 bit FNV-1a, which unlike the standard library's hasher is stable between releases
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Records every tick of the seeded world
+fn record() -> Vec<u8> {
+    let mut world = World::new_with_seed(SEED);
+    let mut recorder = Recorder::new(&world, Vec::new(), KEYFRAME_INTERVAL).unwrap();
+    for _ in 0..TICKS {
+        world.update();
+        recorder.record(&world).unwrap();
+    }
+    recorder.finish().unwrap()
+}
+
+#[test]
+fn seeded_world_matches_recording() {
+    let recording = record();
+    let replay = Replay::read(recording.as_slice()).unwrap();
+    assert_eq!(replay.len() as u64, TICKS + 1);
+    assert_eq!(recording.len(), GOLDEN_LENGTH);
+    assert_eq!(fnv1a(&recording), GOLDEN_HASH);
+}
+
+#[test]
+fn seeded_world_repeats_within_a_run() {
+    assert_eq!(record(), record());
+}
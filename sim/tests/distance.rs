@@ -0,0 +1,48 @@
+use proptest::prelude::*;
+use sim::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use sim::{Coordinates, DistanceMetric};
+
+const METRICS: [DistanceMetric; 3] = [
+    DistanceMetric::Manhattan,
+    DistanceMetric::Chebyshev,
+    DistanceMetric::Euclidean,
+];
+
+/// Allows for floating point error in the Euclidean distance
+const EPSILON: f64 = 1e-9;
+
+fn coordinates() -> impl Strategy<Value = Coordinates> {
+    (0..WORLD_WIDTH, 0..WORLD_HEIGHT).prop_map(|(x, y)| Coordinates::new(x, y).unwrap())
+}
+
+proptest! {
+    #[test]
+    fn distance_to_self_is_zero(a in coordinates()) {
+        for metric in &METRICS {
+            prop_assert_eq!(a.distance(a, *metric), 0.0);
+        }
+    }
+
+    #[test]
+    fn distance_is_symmetric(a in coordinates(), b in coordinates()) {
+        for metric in &METRICS {
+            prop_assert_eq!(a.distance(b, *metric), b.distance(a, *metric));
+        }
+    }
+
+    #[test]
+    fn distance_obeys_triangle_inequality(a in coordinates(), b in coordinates(), c in coordinates()) {
+        for metric in &METRICS {
+            prop_assert!(a.distance(c, *metric) <= a.distance(b, *metric) + b.distance(c, *metric) + EPSILON);
+        }
+    }
+
+    #[test]
+    fn distances_are_ordered(a in coordinates(), b in coordinates()) {
+        let chebyshev = a.distance(b, DistanceMetric::Chebyshev);
+        let euclidean = a.distance(b, DistanceMetric::Euclidean);
+        let manhattan = a.distance(b, DistanceMetric::Manhattan);
+        prop_assert!(chebyshev <= euclidean + EPSILON);
+        prop_assert!(euclidean <= manhattan + EPSILON);
+    }
+}
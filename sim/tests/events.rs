@@ -0,0 +1,185 @@
+use sim::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use sim::colony::Colony;
+use sim::event::Event;
+use sim::pheromone::{Pheromone, PheromoneType};
+use sim::resource::{Resource, ResourceKind, RESOURCE_KINDS};
+use sim::world::World;
+use sim::Coordinates;
+use std::sync::{Arc, Mutex};
+
+/// The most time steps each test waits for its events
+const TICKS: u64 = 500;
+
+/// A world with a colony in the corner, and a single unit of sugar on every other tile, so any ant that leaves the
+/// colony finds food straight away
+fn foraging_world() -> World {
+    food_world(Resource::new_with_capacity(ResourceKind::Sugar, 1, 1))
+}
+
+/// A world with a colony in the corner, and a copy of the given resource on every other tile
+fn food_world(resource: Resource) -> World {
+    let colony = Coordinates::new(0, 0).unwrap();
+    let food = (0..WORLD_WIDTH)
+        .flat_map(|x| (0..WORLD_HEIGHT).map(move |y| Coordinates::new(x, y).unwrap()))
+        .filter(|coords| *coords != colony)
+        .map(|coords| (coords, resource))
+        .collect();
+    let mut world = World::new_with_data(food, vec![Colony::new(colony)]);
+    world.set_seed(34);
+    world
+}
+
+/// Runs the world until an event matching the predicate is emitted, and returns it
+fn run_until<P: Fn(&Event) -> bool>(world: &mut World, predicate: P) -> Option<Event> {
+    for _ in 0..TICKS {
+        world.update();
+        if let Some(event) = world.get_events().iter().find(|event| predicate(event)) {
+            return Some(*event);
+        }
+    }
+    None
+}
+
+#[test]
+fn picking_up_the_last_unit_depletes_the_resource() {
+    let mut world = foraging_world();
+    let picked_up = run_until(&mut world, |event| {
+        matches!(event, Event::FoodPickedUp { .. })
+    });
+    let position = match picked_up {
+        Some(Event::FoodPickedUp { position, kind, .. }) => {
+            assert_eq!(kind, ResourceKind::Sugar);
+            position
+        }
+        _ => panic!("No food was picked up"),
+    };
+    assert!(world
+        .get_events()
+        .contains(&Event::ResourceDepleted { position }));
+    assert!(world.get_tile(position).resource.is_none());
+}
+
+#[test]
+fn returning_with_food_delivers_it_to_the_colony() {
+    let mut world = foraging_world();
+    let delivered = run_until(&mut world, |event| {
+        matches!(event, Event::FoodDelivered { .. })
+    });
+    match delivered {
+        Some(Event::FoodDelivered {
+            colony,
+            position,
+            kind,
+            ..
+        }) => {
+            assert_eq!(colony, 0);
+            assert_eq!(position, world.get_colonies()[0].get_position());
+            assert_eq!(kind, ResourceKind::Sugar);
+            assert!(world.get_colonies()[0].get_stock(ResourceKind::Sugar) > 0);
+        }
+        _ => panic!("No food was delivered"),
+    }
+}
+
+#[test]
+fn ants_carrying_food_do_not_wait_on_more() {
+    // Protein takes several ticks to pick up, and there is too much of it for any tile to run out
+    let mut world = food_world(Resource::new_with_capacity(
+        ResourceKind::Protein,
+        100_000,
+        100_000,
+    ));
+    for _ in 0..TICKS {
+        let loaded: Vec<_> = world
+            .iter_ants()
+            .filter(|info| info.ant.has_found_food())
+            .map(|info| (info.colony, info.id, info.ant.get_position()))
+            .filter(|(_, _, position)| world.get_tile(*position).resource.is_some())
+            .collect();
+        world.update();
+        for (colony, id, position) in loaded {
+            assert!(
+                world.get_events().contains(&Event::FoodPickedUp {
+                    colony,
+                    id,
+                    position,
+                    kind: ResourceKind::Protein
+                }),
+                "Ant {} waited at {}, while carrying food",
+                id,
+                position
+            );
+        }
+    }
+}
+
+#[test]
+fn no_more_food_is_delivered_than_picked_up() {
+    // Seeded worlds have many ants on few resources, so several ants often reach the last of a resource together
+    for seed in 0..50 {
+        let mut world = World::new_with_seed(seed);
+        let (mut picked_up, mut delivered, mut nutrition) = (0, 0, 0);
+        for _ in 0..TICKS {
+            world.update();
+            for event in world.get_events() {
+                match event {
+                    Event::FoodPickedUp { .. } => picked_up += 1,
+                    Event::FoodDelivered { kind, .. } => {
+                        delivered += 1;
+                        nutrition += kind.get_nutritional_value() as u32;
+                    }
+                    _ => {}
+                }
+            }
+            assert!(
+                delivered <= picked_up,
+                "Seed {} delivered {} food by tick {}, but only picked up {}",
+                seed,
+                delivered,
+                world.get_tick(),
+                picked_up
+            );
+        }
+        // Spawning is free, so the stock holds exactly the food delivered
+        let stock: u32 = world
+            .get_colonies()
+            .iter()
+            .flat_map(|colony| {
+                RESOURCE_KINDS
+                    .iter()
+                    .map(move |kind| colony.get_stock(*kind))
+            })
+            .sum();
+        assert_eq!(stock, nutrition);
+    }
+}
+
+#[test]
+fn decayed_pheromones_expire() {
+    let mut world = World::new_with_data(Vec::new(), Vec::new());
+    let position = Coordinates::new(5, 5).unwrap();
+    world.add_pheromone(
+        position,
+        Pheromone::new(1, 1, PheromoneType::Exploration).unwrap(),
+    );
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&events);
+    world.add_observer(Box::new(move |tick: u64, event: &Event| {
+        recorded.lock().unwrap().push((tick, *event))
+    }));
+    // The first update takes the pheromone to zero strength, and the second removes it
+    world.update();
+    assert!(events.lock().unwrap().is_empty());
+    world.update();
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec![(
+            1,
+            Event::PheromoneExpired {
+                position,
+                pheromone_type: PheromoneType::Exploration
+            }
+        )]
+    );
+}
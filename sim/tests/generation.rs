@@ -0,0 +1,25 @@
+use proptest::prelude::*;
+use sim::generation::{generate, get_reachable_tiles, GeneratorSettings};
+
+/// The most colonies placed in a generated world
+const MAXIMUM_COLONIES: u8 = 8;
+
+proptest! {
+    #[test]
+    fn generated_worlds_are_connected(seed in any::<u64>(), colonies in 1..=MAXIMUM_COLONIES) {
+        let settings = GeneratorSettings { colonies, ..GeneratorSettings::default() };
+        let world = generate(seed, &settings);
+        prop_assert_eq!(world.get_colonies().len(), colonies as usize);
+
+        let reachable = get_reachable_tiles(&world, world.get_colonies()[0].get_position());
+        for colony in world.get_colonies() {
+            let position = colony.get_position();
+            prop_assert!(reachable.contains(&position), "Colony at {} can't be reached", position);
+            prop_assert!(world.get_terrain(position).is_passable(), "Colony at {} is on a wall", position);
+        }
+        for (position, _) in world.iter_resources() {
+            prop_assert!(reachable.contains(&position), "Resource at {} can't be reached", position);
+            prop_assert!(world.get_terrain(position).is_passable(), "Resource at {} is on a wall", position);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use sim::colony::Colony;
+use sim::replay::{Recorder, Replay};
+use sim::snapshot::WorldSnapshot;
+use sim::world::World;
+use sim::Coordinates;
+
+/// The number of frames between keyframes, long enough that every edit lands between two of them
+const KEYFRAME_INTERVAL: u32 = 100;
+
+#[test]
+fn removing_a_colony_while_recording_plays_back() {
+    let colonies = vec![
+        Colony::new(Coordinates::new(3, 3).unwrap()),
+        Colony::new(Coordinates::new(12, 12).unwrap()),
+    ];
+    let mut world = World::new_with_data(Vec::new(), colonies);
+    world.set_seed(39);
+    let mut recorder = Recorder::new(&world, Vec::new(), KEYFRAME_INTERVAL).unwrap();
+    let mut expected = vec![WorldSnapshot::new(&world)];
+    for tick in 0..30 {
+        match tick {
+            10 => assert!(world.remove_colony(0).is_some()),
+            20 => {
+                world.add_colony(Colony::new(Coordinates::new(3, 12).unwrap()));
+            }
+            _ => {}
+        }
+        world.update();
+        recorder.record(&world).unwrap();
+        expected.push(WorldSnapshot::new(&world));
+    }
+
+    let replay = Replay::read(recorder.finish().unwrap().as_slice()).unwrap();
+    assert_eq!(replay.len(), expected.len());
+    for (index, snapshot) in expected.iter().enumerate() {
+        assert_eq!(
+            replay.get_snapshot(index).as_ref(),
+            Some(snapshot),
+            "Frame {} differs",
+            index
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use sim::colony::Colony;
+use sim::event::Event;
+use sim::resource::{Resource, ResourceKind, ResourcePolicy};
+use sim::world::World;
+use sim::Coordinates;
+
+/// The most time steps to wait for the food to be found
+const TICKS: u64 = 1000;
+
+/// A world with a colony in the corner, and a single unit of protein beside it
+fn single_resource_world(policy: ResourcePolicy) -> (World, Coordinates) {
+    let food = Coordinates::new(1, 0).unwrap();
+    let resource = Resource::new_with_capacity(ResourceKind::Protein, 1, 1);
+    let colony = Colony::new(Coordinates::new(0, 0).unwrap());
+    let mut world = World::new_with_data(vec![(food, resource)], vec![colony]);
+    world.set_seed(44);
+    world.set_resource_policy(policy);
+    (world, food)
+}
+
+/// Runs the world until the resource at the given position is depleted, and returns the tick it happened on
+fn deplete(world: &mut World, position: Coordinates) -> u64 {
+    for _ in 0..TICKS {
+        world.update();
+        if world
+            .get_events()
+            .contains(&Event::ResourceDepleted { position })
+        {
+            return world.get_tick() - 1;
+        }
+    }
+    panic!("The resource was never depleted");
+}
+
+/// Returns the positions of every resource spawned by the policy during the last time step
+fn spawned(world: &World) -> Vec<Coordinates> {
+    world
+        .get_events()
+        .iter()
+        .filter_map(|event| match event {
+            Event::ResourceSpawned { position } => Some(*position),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn regrow_replaces_depleted_resources_in_place() {
+    let policy = ResourcePolicy::Regrow {
+        interval: 10,
+        amount: 5,
+    };
+    let (mut world, food) = single_resource_world(policy);
+    let depleted_at = deplete(&mut world, food);
+
+    // Resources only regrow at the end of every interval
+    while spawned(&world).is_empty() {
+        assert!(world.get_tick() < depleted_at + 10);
+        world.update();
+    }
+    assert_eq!(spawned(&world), vec![food]);
+    let resource = world.get_tile(food).resource.unwrap();
+    assert_eq!(resource.get_kind(), ResourceKind::Protein);
+    assert_eq!(resource.get_resources_remaining(), 1);
+    assert_eq!(resource.get_capacity(), 1);
+}
+
+#[test]
+fn regrow_stops_at_each_resources_own_capacity() {
+    let small = Coordinates::new(3, 3).unwrap();
+    let large = Coordinates::new(9, 9).unwrap();
+    let mut world = World::new_with_data(
+        vec![
+            (
+                small,
+                Resource::new_with_capacity(ResourceKind::Sugar, 1, 3),
+            ),
+            (
+                large,
+                Resource::new_with_capacity(ResourceKind::Water, 1, 50),
+            ),
+        ],
+        Vec::new(),
+    );
+    world.set_resource_policy(ResourcePolicy::Regrow {
+        interval: 1,
+        amount: 10,
+    });
+    let remaining = |world: &World, position| {
+        world
+            .get_tile(position)
+            .resource
+            .unwrap()
+            .get_resources_remaining()
+    };
+
+    world.update();
+    assert_eq!(remaining(&world, small), 3);
+    assert_eq!(remaining(&world, large), 11);
+    for _ in 0..10 {
+        world.update();
+    }
+    assert_eq!(remaining(&world, small), 3);
+    assert_eq!(remaining(&world, large), 50);
+}
+
+#[test]
+fn respawn_replaces_depleted_resources_elsewhere_after_the_delay() {
+    let delay = 25;
+    let (mut world, food) = single_resource_world(ResourcePolicy::Respawn { delay });
+    let depleted_at = deplete(&mut world, food);
+
+    while world.get_tick() <= depleted_at + delay as u64 {
+        assert!(spawned(&world).is_empty());
+        assert_eq!(world.iter_resources().count(), 0);
+        world.update();
+    }
+    // The resource is placed at the end of the time step, `delay` ticks after it was depleted
+    assert_eq!(world.get_tick() - 1, depleted_at + delay as u64);
+    let positions = spawned(&world);
+    assert_eq!(positions.len(), 1);
+    // Where it goes is random, but decided by the seed, which puts it away from the original tile
+    assert_ne!(positions[0], food);
+    let resource = world.get_tile(positions[0]).resource.unwrap();
+    assert_eq!(resource.get_kind(), ResourceKind::Protein);
+    assert_eq!(resource.get_capacity(), 1);
+}
+
+#[test]
+fn spawned_resources_never_land_on_a_colony() {
+    let colony = Coordinates::new(7, 7).unwrap();
+    let mut world = World::new_with_data(Vec::new(), vec![Colony::new(colony)]);
+    world.set_seed(46);
+    // Enough resources in a single season to fill every other tile
+    world.set_resource_policy(ResourcePolicy::Seasonal {
+        period: 1,
+        count: u8::MAX,
+    });
+    world.update();
+    assert_eq!(world.iter_resources().count(), u8::MAX as usize);
+    assert!(world.get_tile(colony).resource.is_none());
+}
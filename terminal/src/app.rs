@@ -0,0 +1,214 @@
+use crate::view;
+use crossterm::event;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::{execute, terminal};
+use sim::ant::AntType;
+use sim::event::Event as SimEvent;
+use sim::resource::RESOURCE_KINDS;
+use sim::snapshot::WorldSnapshot;
+use sim::world::World;
+use std::io;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+/// The time between ticks at each speed, from slowest to fastest
+///
+/// None runs as many ticks as fit in a single frame
+const TICK_INTERVALS: [Option<Duration>; 7] = [
+    Some(Duration::from_millis(1000)),
+    Some(Duration::from_millis(500)),
+    Some(Duration::from_millis(250)),
+    Some(Duration::from_millis(100)),
+    Some(Duration::from_millis(50)),
+    Some(Duration::from_millis(10)),
+    None,
+];
+/// The index of the speed the app starts at, a tick every quarter second
+const DEFAULT_SPEED: usize = 2;
+/// The time between redraws, when running as fast as possible
+const FRAME_BUDGET: Duration = Duration::from_millis(33);
+
+/// Simulates a world in the terminal, and handles the keyboard controls
+///
+/// * Q/Escape/Ctrl+C - Quit
+/// * Space - Pause or resume
+/// * Right/Period - Step one tick forwards, and pause
+/// * Plus/Minus - Speed up or slow down, up to as fast as possible
+/// * N - Reset with a new world, using a new seed
+pub struct App {
+    world: World,
+    paused: bool,
+    /// The index into `TICK_INTERVALS` of the current speed
+    speed: usize,
+    /// When the last tick was run, to time the next one
+    last_tick: Instant,
+    /// The food delivered to each colony since the world was created
+    delivered: Vec<u64>,
+    quit: bool,
+}
+
+impl App {
+    pub fn new(world: World) -> App {
+        App {
+            world,
+            paused: false,
+            speed: DEFAULT_SPEED,
+            last_tick: Instant::now(),
+            delivered: Vec::new(),
+            quit: false,
+        }
+    }
+
+    /// Runs until the user quits, drawing to the given output
+    ///
+    /// The terminal should already be in raw mode, on the alternate screen
+    pub fn run<W: Write>(&mut self, out: &mut W) -> io::Result<()> {
+        execute!(out, Clear(ClearType::All))?;
+        while !self.quit {
+            view::draw(out, &WorldSnapshot::new(&self.world), &self.sidebar())?;
+            let timeout = match (self.paused, TICK_INTERVALS[self.speed]) {
+                (true, _) => FRAME_BUDGET,
+                (false, Some(interval)) => interval.saturating_sub(self.last_tick.elapsed()),
+                (false, None) => Duration::ZERO,
+            };
+            if event::poll(timeout)? {
+                match event::read()? {
+                    Event::Key(key) if key.kind != KeyEventKind::Release => self.handle_key(key),
+                    Event::Resize(_, _) => execute!(out, Clear(ClearType::All))?,
+                    _ => {}
+                }
+                continue;
+            }
+            if self.paused {
+                continue;
+            }
+            match TICK_INTERVALS[self.speed] {
+                Some(_) => self.tick(),
+                None => {
+                    let start = Instant::now();
+                    while start.elapsed() < FRAME_BUDGET && !event::poll(Duration::ZERO)? {
+                        self.tick();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Advances the world by a single tick, and counts the food delivered
+    fn tick(&mut self) {
+        self.world.update();
+        self.last_tick = Instant::now();
+        for event in self.world.get_events() {
+            if let SimEvent::FoodDelivered { colony, .. } = event {
+                if self.delivered.len() <= *colony {
+                    self.delivered.resize(colony + 1, 0);
+                }
+                self.delivered[*colony] += 1;
+            }
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Right | KeyCode::Char('.') => {
+                self.paused = true;
+                self.tick();
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.speed = (self.speed + 1).min(TICK_INTERVALS.len() - 1);
+            }
+            KeyCode::Char('-') => self.speed = self.speed.saturating_sub(1),
+            KeyCode::Char('n') => {
+                self.world = World::new();
+                self.delivered.clear();
+            }
+            _ => {}
+        }
+    }
+
+    /// The lines of text shown beside the world
+    fn sidebar(&self) -> Vec<String> {
+        let speed = match TICK_INTERVALS[self.speed] {
+            Some(interval) => format!("{} ticks/s", 1000 / interval.as_millis()),
+            None => String::from("max"),
+        };
+        let resources: u64 = self
+            .world
+            .iter_resources()
+            .map(|(_, resource)| resource.get_resources_remaining() as u64)
+            .sum();
+        let mut lines = vec![
+            format!("Tick: {}", self.world.get_tick()),
+            format!("Seed: {}", self.world.get_seed()),
+            format!(
+                "Speed: {}{}",
+                speed,
+                if self.paused { " (paused)" } else { "" }
+            ),
+            format!("Food remaining: {}", resources),
+        ];
+        for (index, colony) in self.world.get_colonies().iter().enumerate() {
+            let count = |ant_type| {
+                colony
+                    .iter_ants()
+                    .filter(|(_, ant)| ant.get_ant_type() == ant_type)
+                    .count()
+            };
+            lines.push(String::new());
+            lines.push(format!("Colony {} at {}", index, colony.get_position()));
+            lines.push(format!(
+                "  Scouts: {}/{}",
+                count(AntType::Scout),
+                colony.get_target_population(AntType::Scout)
+            ));
+            lines.push(format!(
+                "  Workers: {}/{}",
+                count(AntType::Worker),
+                colony.get_target_population(AntType::Worker)
+            ));
+            lines.push(format!(
+                "  Food delivered: {}",
+                self.delivered.get(index).copied().unwrap_or(0)
+            ));
+            lines.push(format!(
+                "  Stock: {}",
+                RESOURCE_KINDS
+                    .iter()
+                    .map(|kind| format!("{} {}", kind, colony.get_stock(*kind)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        lines.push(String::new());
+        lines.push(String::from("Space pause, Right step, +/- speed"));
+        lines.push(String::from("N new world, Q quit"));
+        lines
+    }
+}
+
+/// Puts the terminal into raw mode on the alternate screen, and restores it when dropped, even after a panic
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn new<W: Write>(out: &mut W) -> io::Result<TerminalGuard> {
+        terminal::enable_raw_mode()?;
+        execute!(out, terminal::EnterAlternateScreen, crossterm::cursor::Hide)?;
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(
+            io::stdout(),
+            crossterm::cursor::Show,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
@@ -0,0 +1,48 @@
+use sim::generation;
+use sim::generation::GeneratorSettings;
+use sim::map;
+use sim::world::World;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+mod app;
+mod view;
+fn main() {
+    // Either `--seed <seed>` to start a new world with the given seed, `--map <path>` to start from a map file, or
+    // `--generate <seed>` to start from a world procedurally generated with the given seed
+    let args: Vec<String> = std::env::args().collect();
+    let world = match (args.get(1).map(String::as_str), args.get(2)) {
+        (Some("--seed"), Some(seed)) => match seed.parse() {
+            Ok(seed) => Ok(World::new_with_seed(seed)),
+            Err(_) => {
+                println!("Invalid seed: {}", seed);
+                return;
+            }
+        },
+        (Some("--generate"), Some(seed)) => match seed.parse() {
+            Ok(seed) => Ok(generation::generate(seed, &GeneratorSettings::default())),
+            Err(_) => {
+                println!("Invalid seed: {}", seed);
+                return;
+            }
+        },
+        (Some("--map"), Some(path)) => {
+            File::open(path).and_then(|file| map::load(BufReader::new(file)))
+        }
+        _ => Ok(World::new()),
+    };
+    let world = match world {
+        Ok(world) => world,
+        Err(e) => {
+            println!("Could not open file: {}", e);
+            return;
+        }
+    };
+
+    let mut out = io::stdout();
+    let result =
+        app::TerminalGuard::new(&mut out).and_then(|_guard| app::App::new(world).run(&mut out));
+    if let Err(e) = result {
+        println!("Error occurred: {}", e);
+    }
+}
@@ -0,0 +1,99 @@
+use crossterm::style::{Color, Print, PrintStyledContent, Stylize};
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::{cursor, queue};
+use sim::ant::AntType;
+use sim::ant_settings::{WORLD_HEIGHT, WORLD_WIDTH};
+use sim::palette;
+use sim::palette::Rgba;
+use sim::snapshot::WorldSnapshot;
+use sim::Coordinates;
+use std::io;
+use std::io::Write;
+
+/// The number of columns each tile takes up, so tiles are roughly square
+const TILE_WIDTH: u16 = 2;
+/// The number of columns between the world and the sidebar
+const SIDEBAR_GAP: u16 = 2;
+
+/// What is drawn on a single tile: the glyph, and the colour behind it
+type Cell = (&'static str, Rgba);
+
+/// Converts a colour from the shared palette into one crossterm can print
+fn to_color(rgba: Rgba) -> Color {
+    Color::Rgb {
+        r: rgba[0],
+        g: rgba[1],
+        b: rgba[2],
+    }
+}
+
+/// Returns what to draw on every tile, with the same layer order as the window renderer, so resources are drawn
+/// over colonies, which are drawn over ants, and so on
+fn cells(snapshot: &WorldSnapshot) -> Vec<Vec<Cell>> {
+    let mut cells =
+        vec![vec![("  ", palette::BORDER_COLOR); WORLD_HEIGHT as usize]; WORLD_WIDTH as usize];
+    let mut set = |coords: Coordinates, cell: Cell| {
+        cells[coords.get_x_position_usize()][coords.get_y_position_usize()] = cell;
+    };
+    for (coords, terrain) in snapshot.iter_terrain() {
+        if let Some(color) = palette::get_terrain_color(terrain) {
+            set(coords, ("  ", color));
+        }
+    }
+    for (coords, pheromone) in snapshot.iter_pheromones() {
+        set(coords, ("  ", palette::get_pheromone_color(pheromone)));
+    }
+    for (_colony, ant) in snapshot.iter_ants() {
+        let glyph = match ant.ant_type {
+            AntType::Scout => "s ",
+            AntType::Worker => "w ",
+        };
+        set(ant.position, (glyph, palette::get_ant_color(ant.ant_type)));
+    }
+    for colony in snapshot.get_colonies() {
+        set(colony.position, ("()", palette::COLONY_COLOR));
+    }
+    for (coords, resource) in snapshot.iter_resources() {
+        set(coords, ("<>", palette::get_resource_color(resource)));
+    }
+    cells
+}
+
+/// Draws the world in the top left of the terminal, with the given lines of text in a sidebar to its right
+pub fn draw<W: Write>(out: &mut W, snapshot: &WorldSnapshot, sidebar: &[String]) -> io::Result<()> {
+    let cells = cells(snapshot);
+    for y in 0..WORLD_HEIGHT {
+        queue!(out, cursor::MoveTo(0, y))?;
+        for column in &cells {
+            let (glyph, background) = column[y as usize];
+            queue!(
+                out,
+                PrintStyledContent(glyph.with(Color::Black).on(to_color(background)))
+            )?;
+        }
+    }
+    let left = WORLD_WIDTH * TILE_WIDTH + SIDEBAR_GAP;
+    for (row, line) in sidebar.iter().enumerate() {
+        queue!(
+            out,
+            cursor::MoveTo(left, row as u16),
+            Print(line),
+            Clear(ClearType::UntilNewLine)
+        )?;
+    }
+    // Clear any lines left over from a longer sidebar
+    for row in sidebar.len() as u16..WORLD_HEIGHT {
+        queue!(
+            out,
+            cursor::MoveTo(left, row),
+            Clear(ClearType::UntilNewLine)
+        )?;
+    }
+    let bottom = WORLD_HEIGHT.max(sidebar.len() as u16);
+    queue!(
+        out,
+        cursor::MoveTo(0, bottom),
+        Clear(ClearType::FromCursorDown)
+    )?;
+    out.flush()
+}